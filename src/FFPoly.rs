@@ -1,14 +1,32 @@
+use crate::error::Error;
+use crate::extension::ExtensionField;
 use crate::ffield_unit::FFieldUnit;
-use std::ops::{Add, Div, Mul, Sub};
-
+use crate::field::{batch_inverse, Field};
+use crate::ntt::{convolve, intt_with_root, ntt_with_root, subgroup_generator};
+use crate::subproduct::SubproductTree;
+use crate::utils::parallel_map;
+use num_traits::{One, Pow, Zero};
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+/// polynomial in var over F, coeffs from the constant term up.
+///
+/// implements num-traits' Zero, One, Pow<u32> and Pow<u64>. Num is left out because
+/// `/` is exact division that panics on a non-zero remainder (`qdiv` and `%` give the
+/// Euclidean pair) and there is no radix notation to parse. Inv is left out because
+/// only non-zero constants have an inverse (see `inverse_mod` and `inverse_series`).
 #[derive(Debug, PartialEq)]
-pub struct FFPoly {
+pub struct FFPoly<F: Field = FFieldUnit> {
     pub var: String,
-    pub coeffs: Vec<FFieldUnit>,
+    pub coeffs: Vec<F>,
 }
 
-impl Clone for FFPoly {
-    fn clone(&self) -> FFPoly {
+impl<F: Field> Clone for FFPoly<F> {
+    fn clone(&self) -> FFPoly<F> {
         Self {
             var: self.var.clone(),
             coeffs: self.coeffs.clone(),
@@ -16,15 +34,15 @@ impl Clone for FFPoly {
     }
 }
 
-impl FFPoly {
-    pub fn new(cfs: Vec<FFieldUnit>, vr: &str) -> Self {
+impl<F: Field> FFPoly<F> {
+    pub fn new(cfs: Vec<F>, vr: &str) -> Self {
         Self {
             var: String::from(vr),
-            coeffs: Self::rm_trailing_with(&cfs, &FFieldUnit::zero()),
+            coeffs: Self::rm_trailing_with(&cfs, &F::zero()),
         }
     }
 
-    pub fn new_no_trim(cfs: Vec<FFieldUnit>, vr: &str) -> Self {
+    pub fn new_no_trim(cfs: Vec<F>, vr: &str) -> Self {
         Self {
             var: String::from(vr),
             coeffs: cfs,
@@ -32,37 +50,33 @@ impl FFPoly {
     }
 
     pub fn zero(v: &str) -> Self {
-        Self::new(vec![FFieldUnit::zero()], v)
+        Self::new(vec![F::zero()], v)
     }
 
     pub fn degree(&self) -> usize {
-        self.coeffs.len().checked_sub(1).unwrap_or(0)
+        self.coeffs.len().saturating_sub(1)
     }
 
-    pub fn get_coeff_at_degree(&self, n: usize) -> FFieldUnit {
-        if n <= self.degree() {
-            self.coeffs[n].clone()
-        } else {
-            FFieldUnit::zero()
-        }
+    pub fn get_coeff_at_degree(&self, n: usize) -> F {
+        self.coeffs.get(n).copied().unwrap_or_else(F::zero)
     }
 
-    pub fn scalar_mul(&self, v: &FFieldUnit) -> Self {
-        let nw_coeffs = self.coeffs.iter().map(|c| c.clone() * v.clone()).collect();
+    pub fn scalar_mul(&self, v: &F) -> Self {
+        let nw_coeffs = self.coeffs.iter().map(|c| *c * *v).collect();
         Self::new(nw_coeffs, &self.var)
     }
 
     fn trim_trailing_zeroes(&self) -> Self {
         Self {
             var: self.var.clone(),
-            coeffs: Self::rm_trailing_with(&self.coeffs, &FFieldUnit::zero()),
+            coeffs: Self::rm_trailing_with(&self.coeffs, &F::zero()),
         }
     }
 
-    fn rm_trailing_with(v: &[FFieldUnit], w: &FFieldUnit) -> Vec<FFieldUnit> {
+    fn rm_trailing_with(v: &[F], w: &F) -> Vec<F> {
         let mut res = v.to_vec();
         while let Some(&l) = res.last() {
-            if l == w.clone() {
+            if l == *w {
                 res.pop();
             } else {
                 break;
@@ -71,69 +85,351 @@ impl FFPoly {
         res
     }
 
-    pub fn eval(&self, vr_val: &FFieldUnit) -> FFieldUnit {
-        let val = vr_val.clone();
+    pub fn eval(&self, vr_val: &F) -> F {
+        let val = *vr_val;
 
         self.coeffs
             .iter()
             .rev()
-            .fold(FFieldUnit::zero(), |acc, coef| {
-                acc * val.clone() + coef.clone()
-            })
+            .fold(F::zero(), |acc, coef| acc * val + *coef)
+    }
+
+    /// evaluates at a point of an extension of the coefficient field.
+    pub fn eval_ext<E: ExtensionField<Base = F>>(&self, vr_val: &E) -> E {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(E::zero(), |acc, coef| acc * *vr_val + E::from_base(*coef))
     }
 
     pub fn compose(&self, other: &Self) -> Self {
-        let mut res = Self::new(vec![FFieldUnit::zero()], &self.var);
+        let mut res = Self::new(vec![F::zero()], &self.var);
         for coef in self.coeffs.iter().rev() {
-            res = (res.clone() * other.clone()) + Self::new(vec![coef.clone()], &self.var);
+            res = (res.clone() * other.clone()) + Self::new(vec![*coef], &self.var);
         }
         res.trim_trailing_zeroes()
     }
 
-    pub fn monomial(deg: usize, coef: FFieldUnit, var: &str) -> Self {
-        let mut coeffs: Vec<FFieldUnit> =
-            (0..deg).into_iter().map(|_| FFieldUnit::zero()).collect();
+    /// `multi_eval` on up to jobs threads, each taking one contiguous chunk of the
+    /// points. a subgroup in natural order is not split, as its single NTT beats any
+    /// chunking.
+    pub fn eval_many(&self, points: &[F], jobs: usize) -> Vec<F>
+    where
+        F: Send + Sync,
+    {
+        if jobs <= 1 || subgroup_generator(points).is_some() {
+            return self.multi_eval(points);
+        }
+        let chunk_len = points.len().div_ceil(jobs).max(1);
+        let chunks: Vec<&[F]> = points.chunks(chunk_len).collect();
+        parallel_map(&chunks, jobs, |_, chunk| self.multi_eval(chunk)).concat()
+    }
+
+    /// scaled to leading coefficient one, the zero polynomial stays zero.
+    pub fn monic(&self) -> Self {
+        match Self::rm_trailing_with(&self.coeffs, &F::zero()).last() {
+            Some(lead) => self.scalar_mul(&lead.inverse()),
+            None => Self::new(vec![], &self.var),
+        }
+    }
+
+    /// monic greatest common divisor, zero only when both inputs are zero.
+    pub fn gcd(&self, other: &Self) -> Self {
+        // trimmed, so that an untrimmed zero ends the loop instead of being divided by
+        let (mut a, mut b) = (self.trim_trailing_zeroes(), other.trim_trailing_zeroes());
+        while !b.coeffs.is_empty() {
+            let r = a.qdiv(&b).1;
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    /// (g, s, t) with s * self + t * other = g and g = gcd(self, other) monic.
+    pub fn xgcd(&self, other: &Self) -> (Self, Self, Self) {
+        let var = self.var.as_str();
+        let (mut r0, mut r1) = (self.trim_trailing_zeroes(), other.trim_trailing_zeroes());
+        let (mut s0, mut s1) = (Self::new(vec![F::one()], var), Self::zero(var));
+        let (mut t0, mut t1) = (Self::zero(var), Self::new(vec![F::one()], var));
+        while !r1.coeffs.is_empty() {
+            let (q, r) = r0.qdiv(&r1);
+            let s = s0 - q.clone() * s1.clone();
+            let t = t0 - q * t1.clone();
+            (r0, r1) = (r1, r);
+            (s0, s1) = (s1, s);
+            (t0, t1) = (t1, t);
+        }
+        match r0.coeffs.last() {
+            Some(lead) => {
+                let inv = lead.inverse();
+                (
+                    r0.scalar_mul(&inv),
+                    s0.scalar_mul(&inv),
+                    t0.scalar_mul(&inv),
+                )
+            }
+            None => (r0, s0, t0),
+        }
+    }
+
+    /// g with self * g = 1 mod modulus, reduced below the degree of modulus.
+    /// Error::NotInvertible when self and modulus share a factor.
+    pub fn inverse_mod(&self, modulus: &Self) -> Result<Self, Error> {
+        let (_, reduced) = self.try_qdiv(modulus)?;
+        let (g, s, _) = reduced.xgcd(modulus);
+        if g.coeffs != vec![F::one()] {
+            return Err(Error::NotInvertible);
+        }
+        Ok(s.qdiv(modulus).1)
+    }
+
+    /// formal derivative.
+    pub fn derivative(&self) -> Self {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| *c * F::from_u128(i as u128))
+            .collect();
+        Self::new(coeffs, &self.var)
+    }
+
+    /// evaluates at every point: an NTT when the points are a subgroup in natural
+    /// order, a subproduct tree when there are many points and coefficients, Horner's
+    /// rule per point otherwise. `eval_many` spreads the same work over threads.
+    pub fn multi_eval(&self, points: &[F]) -> Vec<F> {
+        if let Some(g) = subgroup_generator(points) {
+            let domain_size = points.len();
+            let mut vals = vec![F::zero(); domain_size];
+            for (i, c) in self.coeffs.iter().enumerate() {
+                vals[i % domain_size] = vals[i % domain_size] + *c;
+            }
+            ntt_with_root(&mut vals, g);
+            return vals;
+        }
+        if points.len().min(self.coeffs.len()) >= SUBPRODUCT_THRESHOLD {
+            return SubproductTree::new(points, &self.var).evaluate(self);
+        }
+        points.iter().map(|x| self.eval(x)).collect()
+    }
+
+    pub fn monomial(deg: usize, coef: F, var: &str) -> Self {
+        let mut coeffs: Vec<F> = vec![F::zero(); deg];
         coeffs.push(coef);
         Self::new(coeffs, var)
     }
 
-    pub fn div_euclid_recursive(f: &Self, g: &Self) -> (Self, Self) {
-        let var = f.var.as_str();
-        let (d1, d2) = (f.degree(), g.degree());
-        let c1 = &f.coeffs;
-        let c2 = &g.coeffs;
+    /// quotient and remainder of self / other, Error::DivisionByZero when other is the
+    /// zero polynomial.
+    ///
+    /// schoolbook long division on a single remainder buffer, cancelling the leading
+    /// coefficient once per quotient coefficient, highest degree first.
+    pub fn try_qdiv(&self, other: &Self) -> Result<(Self, Self), Error> {
+        let var = self.var.as_str();
+        let divisor = Self::rm_trailing_with(&other.coeffs, &F::zero());
+        let Some(lead) = divisor.last() else {
+            return Err(Error::DivisionByZero);
+        };
+        let mut rem = Self::rm_trailing_with(&self.coeffs, &F::zero());
+        if rem.len() < divisor.len() {
+            return Ok((Self::zero(var), Self::new(rem, var)));
+        }
 
-        if d1 < d2 {
-            (Self::zero(var), f.clone())
-        } else if d1 == 0 {
-            if c1.get(0).unwrap_or(&FFieldUnit::zero()) == &FFieldUnit::zero() {
-                panic!("division by 0!");
+        let lead_inv = lead.inverse();
+        let d = divisor.len() - 1;
+        let mut quot = vec![F::zero(); rem.len() - d];
+        for i in (0..quot.len()).rev() {
+            let c = rem[i + d] * lead_inv;
+            quot[i] = c;
+            if c.is_zero() {
+                continue;
+            }
+            for (r, g) in rem[i..=i + d].iter_mut().zip(divisor.iter()) {
+                *r = *r - c * *g;
             }
-            (Self::new_no_trim(vec![c1[0] / c2[0]], var), Self::zero(var))
-        } else {
-            let [c_a, c_b] = [c1[d1], c2[d2]];
-            let mut q_1 = Self::new_no_trim(vec![FFieldUnit::zero(); d1 - d2 + 1], var);
-            q_1.coeffs[d1 - d2] = c_a / c_b;
-            let h_2 = f.clone() - q_1.clone() * g.clone();
-            let (q_2, r) =
-                Self::div_euclid_recursive(&h_2.trim_trailing_zeroes(), &g.trim_trailing_zeroes());
-            (q_1 + q_2, r)
         }
+        rem.truncate(d);
+        Ok((Self::new(quot, var), Self::new(rem, var)))
     }
 
+    /// same as `try_qdiv`, panics when other is the zero polynomial.
     pub fn qdiv(&self, other: &Self) -> (Self, Self) {
-        Self::div_euclid_recursive(&self.trim_trailing_zeroes(), &other.trim_trailing_zeroes())
+        self.try_qdiv(other)
+            .expect("polynomial division by the zero polynomial")
     }
 
-    pub fn gen_linear_term(unit: &FFieldUnit, v: &str) -> Self {
-        Self::new(
-            vec![FFieldUnit::zero() - unit.clone(), FFieldUnit::one()],
-            v,
-        )
+    /// g with self * g = 1 mod x^n, by Newton iteration: g <- g (2 - self g), doubling
+    /// the number of correct coefficients each round. Error::DivisionByZero when the
+    /// constant term is zero, as such a series has no inverse.
+    pub fn inverse_series(&self, n: usize) -> Result<Self, Error> {
+        let c0 = match self.coeffs.first() {
+            Some(c) if !c.is_zero() => *c,
+            _ => return Err(Error::DivisionByZero),
+        };
+        let mut g = vec![c0.inverse()];
+        let mut k = 1;
+        while k < n {
+            k = (2 * k).min(n);
+            let mut e = mul_coeffs(&self.coeffs[..self.coeffs.len().min(k)], &g);
+            e.resize(k, F::zero());
+            for c in e.iter_mut() {
+                *c = c.neg();
+            }
+            e[0] = e[0] + F::one() + F::one();
+            g = mul_coeffs(&g, &e);
+            g.truncate(k);
+        }
+        g.truncate(n);
+        Ok(Self::new(g, &self.var))
+    }
+
+    /// same result as `qdiv` in O(M(n)) instead of quadratic time: with rev the
+    /// coefficient reversal, rev(q) = rev(self) / rev(other) mod x^(deg q + 1), and the
+    /// remainder follows from one more product. small inputs go to long division.
+    /// panics when other is the zero polynomial.
+    pub fn qdiv_fast(&self, other: &Self) -> (Self, Self) {
+        let f = Self::rm_trailing_with(&self.coeffs, &F::zero());
+        let g = Self::rm_trailing_with(&other.coeffs, &F::zero());
+        if f.len() < g.len() || g.len().min(f.len() - g.len()) < NEWTON_DIV_THRESHOLD {
+            return self.qdiv(other);
+        }
+
+        let k = f.len() - g.len() + 1;
+        let rev_f: Vec<F> = f.iter().rev().take(k).copied().collect();
+        let rev_g = Self::new_no_trim(g.iter().rev().copied().collect(), &self.var);
+        let inv = rev_g
+            .inverse_series(k)
+            .expect("leading coefficient is non-zero");
+        let mut quot = mul_coeffs(&rev_f, &inv.coeffs);
+        quot.resize(k, F::zero());
+        quot.reverse();
+
+        let mut rem = f;
+        for (r, c) in rem.iter_mut().zip(mul_coeffs(&quot, &g)) {
+            *r = *r - c;
+        }
+        rem.truncate(g.len() - 1);
+        (Self::new(quot, &self.var), Self::new(rem, &self.var))
+    }
+
+    /// divides by the linear term (x - a), returning the quotient and the remainder,
+    /// which is self evaluated at a. one pass of Horner's rule.
+    pub fn synthetic_div(&self, a: &F) -> (Self, F) {
+        let mut quot = vec![F::zero(); self.coeffs.len().saturating_sub(1)];
+        let mut acc = F::zero();
+        for (i, c) in self.coeffs.iter().enumerate().rev() {
+            acc = acc * *a + *c;
+            if i > 0 {
+                quot[i - 1] = acc;
+            }
+        }
+        (Self::new(quot, &self.var), acc)
+    }
+
+    pub fn gen_linear_term(unit: &F, v: &str) -> Self {
+        Self::new(vec![F::zero() - *unit, F::one()], v)
+    }
+
+    /// compact binary form: coefficient count as a little-endian u32, followed by
+    /// every coefficient in canonical bytes, lowest degree first. the variable name
+    /// is not encoded, nor are trailing zero coefficients.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let coeffs = Self::rm_trailing_with(&self.coeffs, &F::zero());
+        let mut res = Vec::with_capacity(4 + coeffs.len() * F::NUM_BYTES);
+        res.extend_from_slice(&(coeffs.len() as u32).to_le_bytes());
+        for c in coeffs.iter() {
+            res.extend(c.to_canonical_bytes());
+        }
+        res
+    }
+
+    /// inverse of `to_bytes`, every coefficient must be canonical and the leading one
+    /// non-zero, so that every polynomial has exactly one encoding.
+    pub fn from_bytes(bytes: &[u8], var: &str) -> Result<Self, Error> {
+        if bytes.len() < 4 {
+            return Err(Error::InvalidLength);
+        }
+        let (len, body) = bytes.split_at(4);
+        let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+        if body.len() != len * F::NUM_BYTES {
+            return Err(Error::InvalidLength);
+        }
+        let coeffs = body
+            .chunks_exact(F::NUM_BYTES)
+            .map(F::from_canonical_bytes)
+            .collect::<Result<Vec<F>, Error>>()?;
+        if coeffs.last().is_some_and(|c| c.is_zero()) {
+            return Err(Error::NonCanonical);
+        }
+        Ok(Self::new_no_trim(coeffs, var))
     }
 }
 
-impl Add<FFPoly> for FFPoly {
+/// non-zero terms from the highest degree down, coefficient first, e.g.
+/// "5*x^2 + 1*x + 3"; the zero polynomial is "0".
+impl<F: Field> fmt::Display for FFPoly<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let terms: Vec<String> = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| !c.is_zero())
+            .map(|(i, c)| match i {
+                0 => format!("{}", c),
+                1 => format!("{}*{}", c, self.var),
+                _ => format!("{}*{}^{}", c, self.var, i),
+            })
+            .collect();
+        if terms.is_empty() {
+            return write!(f, "0");
+        }
+        write!(f, "{}", terms.join(" + "))
+    }
+}
+
+/// inverse of `Display`: terms "c", "c*v" or "c*v^k" joined by " + " in any order,
+/// all in the same variable v. repeated degrees are added up. the variable of a
+/// constant polynomial cannot be recovered and defaults to "x".
+impl<F: Field + FromStr<Err = Error>> FromStr for FFPoly<F> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut var: Option<&str> = None;
+        let mut terms: Vec<(usize, F)> = Vec::new();
+        for term in s.split(" + ") {
+            let (coeff, power) = match term.split_once('*') {
+                None => (term, None),
+                Some((c, p)) => (c, Some(p)),
+            };
+            let degree = match power {
+                None => 0,
+                Some(p) => {
+                    let (v, k) = match p.split_once('^') {
+                        None => (p, 1),
+                        Some((v, k)) => (v, k.parse().map_err(|_| Error::InvalidDigit)?),
+                    };
+                    if v.is_empty() || var.is_some_and(|var| var != v) {
+                        return Err(Error::InvalidDigit);
+                    }
+                    var = Some(v);
+                    k
+                }
+            };
+            terms.push((degree, coeff.parse()?));
+        }
+
+        let len = terms.iter().map(|(d, _)| d + 1).max().unwrap_or(0);
+        let mut coeffs = vec![F::zero(); len];
+        for (d, c) in terms {
+            coeffs[d] = coeffs[d] + c;
+        }
+        Ok(Self::new(coeffs, var.unwrap_or("x")))
+    }
+}
+
+impl<F: Field> Add<FFPoly<F>> for FFPoly<F> {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
@@ -145,52 +441,37 @@ impl Add<FFPoly> for FFPoly {
 
         let mut result = longer.coeffs.clone();
         for (i, coef) in shorter.coeffs.iter().enumerate() {
-            result[i] = result[i].clone() + coef.clone();
+            result[i] = result[i] + *coef;
         }
         Self::new(result, &self.var)
     }
 }
 
-impl Sub<FFPoly> for FFPoly {
+impl<F: Field> Sub<FFPoly<F>> for FFPoly<F> {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self {
-        let (longer, shorter) = if self.coeffs.len() >= other.coeffs.len() {
-            (self.clone(), other)
-        } else {
-            (other, self.clone())
-        };
-        // we can make utility fn out of this
-        let result: Vec<FFieldUnit> = longer
-            .coeffs
-            .iter()
-            .enumerate()
-            .map(|(i, x)| x.clone() - shorter.coeffs.get(i).unwrap_or(&FFieldUnit::zero()).clone())
+        let len = self.coeffs.len().max(other.coeffs.len());
+        let result: Vec<F> = (0..len)
+            .map(|i| self.get_coeff_at_degree(i) - other.get_coeff_at_degree(i))
             .collect();
-
         Self::new(result, &self.var)
     }
 }
 
-impl Mul<FFPoly> for FFPoly {
+impl<F: Field> Mul<FFPoly<F>> for FFPoly<F> {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self {
-        let mut result = vec![FFieldUnit::zero(); self.coeffs.len() + other.coeffs.len() - 1];
-
-        for (i, coef1) in self.coeffs.iter().enumerate() {
-            for (j, coef2) in other.coeffs.iter().enumerate() {
-                result[i + j] = result[i + j].clone() + (coef1.clone() * coef2.clone());
-            }
-        }
+        let result = mul_coeffs(&self.coeffs, &other.coeffs);
         Self::new(result, &self.var).trim_trailing_zeroes()
     }
 }
 
-impl Mul<FFieldUnit> for FFPoly {
+impl<F: Field> Mul<F> for FFPoly<F> {
     type Output = Self;
 
-    fn mul(self, scalar: FFieldUnit) -> Self {
+    fn mul(self, scalar: F) -> Self {
         self.scalar_mul(&scalar)
     }
 }
@@ -203,7 +484,7 @@ impl Mul<FFPoly> for FFieldUnit {
     }
 }
 
-impl Div<FFPoly> for FFPoly {
+impl<F: Field> Div<FFPoly<F>> for FFPoly<F> {
     type Output = Self;
 
     fn div(self, other: Self) -> Self {
@@ -214,88 +495,363 @@ impl Div<FFPoly> for FFPoly {
     }
 }
 
-impl Div<FFieldUnit> for FFPoly {
+impl<F: Field> Rem<FFPoly<F>> for FFPoly<F> {
+    type Output = Self;
+
+    /// remainder of the Euclidean division, panics when other is zero.
+    fn rem(self, other: Self) -> Self {
+        self.qdiv(&other).1
+    }
+}
+
+impl<F: Field> Div<F> for FFPoly<F> {
     type Output = Self;
 
-    fn div(self, scalar: FFieldUnit) -> Self {
-        let scalar_inv = FFieldUnit::one() / scalar;
+    fn div(self, scalar: F) -> Self {
+        let scalar_inv = F::one() / scalar;
         // return only q
         self.scalar_mul(&scalar_inv)
     }
 }
 
-/// x_vals: &[FFieldUnit]
+impl<F: Field> Neg for FFPoly<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        let coeffs = self.coeffs.iter().map(|c| c.neg()).collect();
+        Self::new_no_trim(coeffs, &self.var)
+    }
+}
+
+impl<F: Field> AddAssign for FFPoly<F> {
+    fn add_assign(&mut self, other: Self) {
+        *self = self.clone() + other;
+    }
+}
+
+impl<F: Field> SubAssign for FFPoly<F> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.clone() - other;
+    }
+}
+
+impl<F: Field> MulAssign for FFPoly<F> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = self.clone() * other;
+    }
+}
+
+impl<F: Field> DivAssign for FFPoly<F> {
+    fn div_assign(&mut self, other: Self) {
+        *self = self.clone() / other;
+    }
+}
+
+impl<F: Field> RemAssign for FFPoly<F> {
+    fn rem_assign(&mut self, other: Self) {
+        *self = self.clone() % other;
+    }
+}
+
+/// polynomials built through num-traits have no variable name to inherit, they use "x".
+impl<F: Field> Zero for FFPoly<F> {
+    fn zero() -> Self {
+        Self::zero("x")
+    }
+
+    fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|c| c.is_zero())
+    }
+}
+
+impl<F: Field> One for FFPoly<F> {
+    fn one() -> Self {
+        Self::new(vec![F::one()], "x")
+    }
+}
+
+impl<F: Field> Sum for FFPoly<F> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, p| acc + p)
+            .unwrap_or_else(<Self as Zero>::zero)
+    }
+}
+
+impl<F: Field> Product for FFPoly<F> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, p| acc * p)
+            .unwrap_or_else(<Self as One>::one)
+    }
+}
+
+impl<F: Field> Pow<u32> for FFPoly<F> {
+    type Output = Self;
+
+    fn pow(self, e: u32) -> Self {
+        Pow::pow(self, e as u64)
+    }
+}
+
+impl<F: Field> Pow<u64> for FFPoly<F> {
+    type Output = Self;
+
+    /// square and multiply.
+    fn pow(self, mut e: u64) -> Self {
+        let mut base = self;
+        let mut res = Self::new(vec![F::one()], &base.var);
+        while e > 0 {
+            if e & 1 == 1 {
+                res *= base.clone();
+            }
+            e >>= 1;
+            if e > 0 {
+                base = base.clone() * base;
+            }
+        }
+        res
+    }
+}
+
+/// below this many coefficients in the shorter operand schoolbook beats Karatsuba.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// from this many coefficients in the shorter operand on, an NTT convolution beats
+/// Karatsuba (when the field has a large enough two-adic subgroup).
+const NTT_THRESHOLD: usize = 256;
+
+/// coefficients of the product a * b, picking schoolbook, Karatsuba or an NTT
+/// convolution by size. all three give the same result.
+pub fn mul_coeffs<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    if a.len().min(b.len()) >= NTT_THRESHOLD {
+        if let Some(res) = convolve(a, b) {
+            return res;
+        }
+    }
+    mul_karatsuba(a, b)
+}
+
+pub fn mul_schoolbook<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut result = vec![F::zero(); a.len() + b.len() - 1];
+    for (i, coef1) in a.iter().enumerate() {
+        for (j, coef2) in b.iter().enumerate() {
+            result[i + j] = result[i + j] + (*coef1 * *coef2);
+        }
+    }
+    result
+}
+
+/// a = a0 + x^m a1, b = b0 + x^m b1, three half-size products instead of four:
+/// a * b = z0 + x^m ((a0 + a1)(b0 + b1) - z0 - z2) + x^2m z2.
+pub fn mul_karatsuba<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+    let m = a.len().max(b.len()) / 2;
+    let (a0, a1) = a.split_at(m.min(a.len()));
+    let (b0, b1) = b.split_at(m.min(b.len()));
+
+    let z0 = mul_karatsuba(a0, b0);
+    let z2 = mul_karatsuba(a1, b1);
+    let mut z1 = mul_karatsuba(&add_coeffs(a0, a1), &add_coeffs(b0, b1));
+    for (i, c) in z0.iter().enumerate() {
+        z1[i] = z1[i] - *c;
+    }
+    for (i, c) in z2.iter().enumerate() {
+        z1[i] = z1[i] - *c;
+    }
+
+    let mut result = vec![F::zero(); a.len() + b.len() - 1];
+    for (z, shift) in [(z0, 0), (z1, m), (z2, 2 * m)] {
+        // anything past the end of result is a (zero) padding coefficient of z1
+        for (r, c) in result[shift.min(a.len() + b.len() - 1)..].iter_mut().zip(z) {
+            *r = *r + c;
+        }
+    }
+    result
+}
+
+fn add_coeffs<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    let (longer, shorter) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut res = longer.to_vec();
+    for (r, c) in res.iter_mut().zip(shorter) {
+        *r = *r + *c;
+    }
+    res
+}
+
+/// below this many coefficients in the divisor or the quotient, `qdiv_fast` falls back
+/// to long division.
+const NEWTON_DIV_THRESHOLD: usize = 64;
+
+/// from this many points and coefficients on, `multi_eval` goes through a subproduct
+/// tree rather than Horner's rule per point.
+const SUBPRODUCT_THRESHOLD: usize = 128;
+
+/// x_vals: &[F]
 ///
-pub fn calc_langrange_polys(x_vals: &[FFieldUnit], var: &str) -> Vec<FFPoly> {
+pub fn calc_langrange_polys<F: Field>(x_vals: &[F], var: &str) -> Vec<FFPoly<F>> {
     let len = x_vals.len();
-    let mut lang_polys = Vec::<FFPoly>::new();
-    let monomials: Vec<FFPoly> = x_vals
+    let mut lang_polys = Vec::<FFPoly<F>>::new();
+    let monomials: Vec<FFPoly<F>> = x_vals
         .iter()
-        .map(|&x| FFPoly::monomial(1, FFieldUnit::one(), var) - FFPoly::monomial(0, x, var))
+        .map(|&x| FFPoly::monomial(1, F::one(), var) - FFPoly::monomial(0, x, var))
         .collect();
     let numerator = prod(&monomials, var);
-    for j in 0..len {
-        //let mut denominator = FFieldUnit::one();
-        let mut v: Vec<FFieldUnit> = Vec::new();
-        for (i, x) in x_vals.iter().enumerate() {
-            if i != j {
-                v.push(x_vals[j].clone() - x.clone());
-            }
-        }
-        let denominator = prod_ffunits(&v);
+    let denominators_inv = batch_inverse(&lagrange_denominators(x_vals));
 
-        let (poly, _) = numerator
-            .clone()
-            .qdiv(&monomials[j].scalar_mul(&denominator));
-        lang_polys.push(poly);
+    for j in 0..len {
+        let (poly, _) = numerator.synthetic_div(&x_vals[j]);
+        lang_polys.push(poly.scalar_mul(&denominators_inv[j]));
     }
     lang_polys
 }
 
-/// y_vals: &[FFieldUnit]
-/// lang_polys: &[FFPoly]
+/// x_vals: &[F]
 ///
-pub fn interpolate_lang_poly(y_vals: &[FFieldUnit], lang_polys: &[FFPoly], var: &str) -> FFPoly {
+/// prod_{i != j} (x_j - x_i) for every j, the denominator of the j-th Lagrange
+/// polynomial. panics if the x-values are not distinct.
+pub fn lagrange_denominators<F: Field>(x_vals: &[F]) -> Vec<F> {
+    let denominators: Vec<F> = (0..x_vals.len())
+        .map(|j| {
+            x_vals
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != j)
+                .fold(F::one(), |acc, (_, x)| acc * (x_vals[j] - *x))
+        })
+        .collect();
+    assert_distinct_points(&denominators);
+    denominators
+}
+
+/// a repeated x-value zeroes the denominators of its Lagrange polynomials, which
+/// batch inversion would silently turn into zero polynomials.
+fn assert_distinct_points<F: Field>(denominators: &[F]) {
+    assert!(
+        denominators.iter().all(|d| !d.is_zero()),
+        "interpolation points must be distinct"
+    );
+}
+
+/// y_vals: &[F]
+/// lang_polys: &[FFPoly<F>]
+///
+pub fn interpolate_lang_poly<F: Field>(
+    y_vals: &[F],
+    lang_polys: &[FFPoly<F>],
+    var: &str,
+) -> FFPoly<F> {
     let mut poly = FFPoly::new(vec![], var);
     for (j, y_val) in y_vals.iter().enumerate() {
-        // println!("i: {}", j);
-        poly = poly + lang_polys[j].clone().scalar_mul(y_val);
+        poly += lang_polys[j].clone().scalar_mul(y_val);
     }
     poly
 }
 
 /// multi threaded version
-/// y_vals: &[FFieldUnit]
-/// lang_polys: &[FFPoly]
+/// x_vals: &[F]
+/// jobs: number of threads
 ///
-pub fn interpolate_lang_poly_threaded(
-    y_vals: &[FFieldUnit],
-    lang_polys: &[FFPoly],
+/// the numerator is built once, the denominators and the per-point divisions are
+/// split across the threads.
+pub fn calc_langrange_polys_threaded<F: Field + Send + Sync>(
+    x_vals: &[F],
     var: &str,
     jobs: usize,
-) -> FFPoly {
-    let mut poly = FFPoly::new(vec![], var);
-    for (j, y_val) in y_vals.iter().enumerate() {
-        // println!("i: {}", j);
-        poly = poly + lang_polys[j].clone().scalar_mul(y_val);
-    }
-    poly
+) -> Vec<FFPoly<F>> {
+    let monomials: Vec<FFPoly<F>> = x_vals
+        .iter()
+        .map(|&x| FFPoly::monomial(1, F::one(), var) - FFPoly::monomial(0, x, var))
+        .collect();
+    let numerator = prod(&monomials, var);
+    let denominators = parallel_map(x_vals, jobs, |j, x_j| {
+        x_vals
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != j)
+            .fold(F::one(), |acc, (_, x)| acc * (*x_j - *x))
+    });
+    assert_distinct_points(&denominators);
+    let denominators_inv = batch_inverse(&denominators);
+
+    parallel_map(x_vals, jobs, |j, x_j| {
+        let (poly, _) = numerator.synthetic_div(x_j);
+        poly.scalar_mul(&denominators_inv[j])
+    })
+}
+
+/// multi threaded version
+/// y_vals: &[F]
+/// lang_polys: &[FFPoly<F>]
+/// jobs: number of threads
+///
+/// every thread sums its chunk of y_j * L_j, the partial sums are added in order.
+pub fn interpolate_lang_poly_threaded<F: Field + Send + Sync>(
+    y_vals: &[F],
+    lang_polys: &[FFPoly<F>],
+    var: &str,
+    jobs: usize,
+) -> FFPoly<F> {
+    assert_eq!(y_vals.len(), lang_polys.len());
+    let chunk_len = y_vals.len().div_ceil(jobs.max(1)).max(1);
+    let chunks: Vec<(&[F], &[FFPoly<F>])> = y_vals
+        .chunks(chunk_len)
+        .zip(lang_polys.chunks(chunk_len))
+        .collect();
+    let partials = parallel_map(&chunks, chunks.len(), |_, (ys, polys)| {
+        interpolate_lang_poly(ys, polys, var)
+    });
+    partials
+        .into_iter()
+        .fold(FFPoly::new(vec![], var), |acc, p| acc + p)
 }
 
-/// x_vals: &[FFieldUnit]
-/// y_vals: &[FFieldUnit]
+/// x_vals: &[F]
+/// y_vals: &[F]
 ///
-pub fn interpolate_poly(x_vals: &[FFieldUnit], y_vals: &[FFieldUnit], var: &str) -> FFPoly {
+/// a full subgroup in natural order is interpolated with an inverse NTT, any other set
+/// of points through a subproduct tree.
+pub fn interpolate_poly<F: Field>(x_vals: &[F], y_vals: &[F], var: &str) -> FFPoly<F> {
     assert_eq!(x_vals.len(), y_vals.len());
 
-    let lang_polys = calc_langrange_polys(x_vals, var);
-    interpolate_lang_poly(y_vals, &lang_polys, var)
+    if let Some(g) = subgroup_generator(x_vals) {
+        let mut coeffs = y_vals.to_vec();
+        intt_with_root(&mut coeffs, g);
+        return FFPoly::new(coeffs, var);
+    }
+    SubproductTree::new(x_vals, var).interpolate(y_vals)
 }
 
-pub fn prod(vals: &[FFPoly], var: &str) -> FFPoly {
+/// the unique polynomial x of degree < deg(prod moduli) with x = residues[i] mod
+/// moduli[i] for every i. the moduli must be pairwise coprime, Error::NotInvertible
+/// otherwise.
+///
+/// incremental (Garner) form: with x solving the first i congruences modulo M,
+/// x + M * ((r_i - x) * M^-1 mod m_i) also solves the i-th.
+pub fn crt<F: Field>(
+    residues: &[FFPoly<F>],
+    moduli: &[FFPoly<F>],
+    var: &str,
+) -> Result<FFPoly<F>, Error> {
+    assert_eq!(residues.len(), moduli.len(), "one residue per modulus");
+    let mut x = FFPoly::zero(var);
+    let mut modulus = FFPoly::new(vec![F::one()], var);
+    for (r, m) in residues.iter().zip(moduli) {
+        let m_inv = modulus.inverse_mod(m)?;
+        let t = ((r.clone() - x.clone()) * m_inv).try_qdiv(m)?.1;
+        x += modulus.clone() * t;
+        modulus *= m.clone();
+    }
+    Ok(x)
+}
+
+pub fn prod<F: Field>(vals: &[FFPoly<F>], var: &str) -> FFPoly<F> {
     let len = vals.len();
     match len {
-        0 => FFPoly::new(vec![FFieldUnit::zero()], var),
+        0 => FFPoly::new(vec![F::zero()], var),
         1 => vals[0].clone(),
         _ => {
             let half = len / 2;
@@ -304,11 +860,11 @@ pub fn prod(vals: &[FFPoly], var: &str) -> FFPoly {
     }
 }
 
-pub fn prod_ffunits(vals: &[FFieldUnit]) -> FFieldUnit {
+pub fn prod_ffunits<F: Field>(vals: &[F]) -> F {
     let len = vals.len();
     match len {
-        0 => FFieldUnit::zero(),
-        1 => vals[0].clone(),
+        0 => F::zero(),
+        1 => vals[0],
         _ => {
             let half = len / 2;
             prod_ffunits(&vals[..half]) * prod_ffunits(&vals[half..])
@@ -321,7 +877,7 @@ mod tests {
     use super::*;
 
     fn get_ffunits_in_range(s: i128, e: i128) -> Vec<FFieldUnit> {
-        (s..e).into_iter().map(|x| FFieldUnit::new(x)).collect()
+        (s..e).map(FFieldUnit::new).collect()
     }
 
     fn get_instance_in_range(s: i128, e: i128) -> FFPoly {
@@ -336,7 +892,11 @@ mod tests {
         assert_eq!(fpoly.var, "x");
         assert_eq!(fpoly.coeffs.len(), 3);
         assert_eq!(
-            fpoly.coeffs.into_iter().map(|x| x.0).collect::<Vec<i128>>(),
+            fpoly
+                .coeffs
+                .into_iter()
+                .map(|x| x.inner())
+                .collect::<Vec<i128>>(),
             [0, 1, 2]
         );
     }
@@ -381,7 +941,7 @@ mod tests {
         let monos: Vec<FFPoly> = units
             .iter()
             .map(|unit| {
-                FFPoly::monomial(1, FFieldUnit::one(), "x") - FFPoly::monomial(0, unit.clone(), "x")
+                FFPoly::monomial(1, FFieldUnit::one(), "x") - FFPoly::monomial(0, *unit, "x")
             })
             .collect();
         assert_eq!(monos.len(), 3);
@@ -406,7 +966,7 @@ mod tests {
         );
         let poly2 = FFPoly::new(vec![FFieldUnit::new(1), FFieldUnit::new(1)], var);
 
-        let (q, r) = FFPoly::div_euclid_recursive(&poly1, &poly2);
+        let (q, r) = poly1.qdiv(&poly2);
 
         let xpected_r = FFPoly::new(vec![FFieldUnit::new(0)], var);
         let xpected_q = FFPoly::new(vec![FFieldUnit::new(2), FFieldUnit::new(1)], var);
@@ -415,6 +975,77 @@ mod tests {
         assert_eq!(r, xpected_r);
     }
 
+    #[test]
+    fn test_division_by_zero_is_an_error() {
+        let p = get_instance_in_range(1, 5);
+        assert_eq!(p.try_qdiv(&FFPoly::zero("x")), Err(Error::DivisionByZero));
+        let trailing_zeroes = FFPoly::new_no_trim(vec![FFieldUnit::zero(); 3], "x");
+        assert_eq!(p.try_qdiv(&trailing_zeroes), Err(Error::DivisionByZero));
+        // zero divided by anything non-zero is fine
+        let (q, r) = FFPoly::zero("x").qdiv(&p);
+        assert_eq!((q, r), (FFPoly::zero("x"), FFPoly::zero("x")));
+    }
+
+    #[test]
+    fn test_long_division_reconstructs_dividend() {
+        let f = get_instance_in_range(-1000, 1047);
+        let g = get_instance_in_range(3, 40);
+        let (q, r) = f.qdiv(&g);
+        assert!(r.degree() < g.degree());
+        assert_eq!(q * g + r, f);
+    }
+
+    #[test]
+    fn test_inverse_series() {
+        let f = get_instance_in_range(3, 50);
+        for n in [1, 2, 7, 64, 100] {
+            let g = f.inverse_series(n).unwrap();
+            let mut prod = (f.clone() * g).coeffs;
+            prod.truncate(n);
+            assert_eq!(
+                FFPoly::new(prod, "x"),
+                FFPoly::new(vec![FFieldUnit::one()], "x")
+            );
+        }
+        let no_constant = get_instance_in_range(0, 5);
+        assert_eq!(no_constant.inverse_series(4), Err(Error::DivisionByZero));
+        let zero: FFPoly = FFPoly::zero("x");
+        assert_eq!(zero.inverse_series(4), Err(Error::DivisionByZero));
+    }
+
+    #[test]
+    fn test_fast_division_matches_long_division() {
+        let f = get_instance_in_range(-1000, 1047);
+        for g in [
+            get_instance_in_range(3, 40),
+            get_instance_in_range(-300, 200),
+            get_instance_in_range(1, 1500),
+            get_instance_in_range(1, 3000),
+        ] {
+            assert_eq!(f.qdiv_fast(&g), f.qdiv(&g));
+        }
+    }
+
+    #[test]
+    fn test_synthetic_division() {
+        let f = get_instance_in_range(-3, 20);
+        let a = FFieldUnit::new(17);
+        let (q, r) = f.synthetic_div(&a);
+        assert_eq!(r, f.eval(&a));
+        assert_eq!(
+            (q.clone(), FFPoly::new(vec![r], "x")),
+            f.qdiv(&FFPoly::gen_linear_term(&a, "x"))
+        );
+        assert_eq!(
+            q * FFPoly::gen_linear_term(&a, "x") + FFPoly::new(vec![r], "x"),
+            f
+        );
+        assert_eq!(
+            FFPoly::<FFieldUnit>::zero("x").synthetic_div(&a),
+            (FFPoly::zero("x"), FFieldUnit::zero())
+        );
+    }
+
     #[test]
     fn test_division_of_polynomials() {
         let v = "x";
@@ -460,9 +1091,241 @@ mod tests {
     fn test_calculation_of_langranges_polynomials() {
         let ffunits = get_ffunits_in_range(2, 5);
         let lng_poly = calc_langrange_polys(&ffunits, "x");
+        assert_eq!(lng_poly.len(), 3);
         //assert_eq!(0, 1, "{:?}", lng_poly);
     }
 
+    #[test]
+    #[should_panic(expected = "interpolation points must be distinct")]
+    fn test_langranges_polynomials_reject_repeated_points() {
+        let ffunits = get_ffunits_in_range(2, 5);
+        calc_langrange_polys(&[ffunits.clone(), vec![ffunits[0]]].concat(), "x");
+    }
+
+    #[test]
+    fn test_multiplication_strategies_agree() {
+        for (la, lb) in [
+            (1, 1),
+            (5, 40),
+            (33, 33),
+            (64, 31),
+            (40, 200),
+            (100, 300),
+            (300, 257),
+        ] {
+            let a = get_ffunits_in_range(-(la as i128), 0);
+            let b = get_ffunits_in_range(7, 7 + lb as i128);
+            let expected = mul_schoolbook(&a, &b);
+            assert_eq!(mul_karatsuba(&a, &b), expected);
+            assert_eq!(mul_coeffs(&a, &b), expected);
+            assert_eq!(convolve(&a, &b), Some(expected));
+        }
+        assert!(mul_coeffs::<FFieldUnit>(&[], &[FFieldUnit::one()]).is_empty());
+    }
+
+    #[test]
+    fn test_large_product_matches_schoolbook() {
+        let p = get_instance_in_range(-500, 523);
+        let q = get_instance_in_range(1, 1024);
+        let expected = FFPoly::new(mul_schoolbook(&p.coeffs, &q.coeffs), "x");
+        assert_eq!(p * q, expected);
+    }
+
+    #[test]
+    fn test_threaded_interpolation_is_deterministic() {
+        let xs = get_ffunits_in_range(-30, 35);
+        let ys = get_ffunits_in_range(7, 72);
+        let serial_polys = calc_langrange_polys(&xs, "x");
+        let serial = interpolate_lang_poly(&ys, &serial_polys, "x");
+        for jobs in [1, 2, 3, 8, 100] {
+            let polys = calc_langrange_polys_threaded(&xs, "x", jobs);
+            assert_eq!(polys, serial_polys);
+            assert_eq!(
+                interpolate_lang_poly_threaded(&ys, &polys, "x", jobs),
+                serial
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "interpolation points must be distinct")]
+    fn test_threaded_langranges_polynomials_reject_repeated_points() {
+        let xs = get_ffunits_in_range(-30, 35);
+        calc_langrange_polys_threaded(&[xs.clone(), vec![xs[40]]].concat(), "x", 4);
+    }
+
+    #[test]
+    fn test_eval_many() {
+        let f = get_instance_in_range(-50, 60);
+        let points = get_ffunits_in_range(1, 250);
+        let expected = f.eval_batch(&points);
+        for jobs in [1, 4, 9] {
+            assert_eq!(f.eval_many(&points, jobs), expected);
+        }
+
+        // chunks large enough for subproduct trees, and a subgroup that stays whole
+        let f = get_instance_in_range(-200, 200);
+        let points = get_ffunits_in_range(1000, 1600);
+        let subgroup = crate::domain::Domain::<FFieldUnit>::subgroup(9)
+            .unwrap()
+            .elements();
+        for points in [points, subgroup] {
+            assert_eq!(f.eval_many(&points, 3), f.eval_batch(&points));
+        }
+    }
+
+    fn from_roots(roots: &[i128]) -> FFPoly {
+        let terms: Vec<FFPoly> = roots
+            .iter()
+            .map(|r| FFPoly::gen_linear_term(&FFieldUnit::new(*r), "x"))
+            .collect();
+        prod(&terms, "x")
+    }
+
+    #[test]
+    fn test_gcd() {
+        let a = from_roots(&[1, 2, 3]).scalar_mul(&FFieldUnit::new(7));
+        let b = from_roots(&[2, 3, 5]);
+        assert_eq!(a.gcd(&b), from_roots(&[2, 3]));
+        assert_eq!(
+            a.gcd(&from_roots(&[4])),
+            FFPoly::new(vec![FFieldUnit::one()], "x")
+        );
+        assert_eq!(a.gcd(&FFPoly::zero("x")), a.monic());
+        let untrimmed_zero = FFPoly::new_no_trim(vec![FFieldUnit::zero(); 3], "x");
+        assert_eq!(a.gcd(&untrimmed_zero), a.monic());
+        assert_eq!(untrimmed_zero.gcd(&a), a.monic());
+        assert_eq!(
+            FFPoly::<FFieldUnit>::zero("x").gcd(&FFPoly::zero("x")),
+            FFPoly::zero("x")
+        );
+
+        // a common root with a vanishing polynomial shows up in the gcd
+        let g = FFieldUnit::generator();
+        let vanishing = FFPoly::monomial(1024, FFieldUnit::one(), "x")
+            + FFPoly::new(vec![FFieldUnit::new(-1)], "x");
+        let numer = FFPoly::gen_linear_term(&FFieldUnit::pow(g, 17), "x") * from_roots(&[9]);
+        assert_eq!(
+            numer.gcd(&vanishing),
+            FFPoly::gen_linear_term(&FFieldUnit::pow(g, 17), "x")
+        );
+    }
+
+    #[test]
+    fn test_xgcd() {
+        let a = get_instance_in_range(-5, 30);
+        let b = from_roots(&[3, 8, 11, 2000]);
+        let (g, s, t) = a.xgcd(&b);
+        assert_eq!(g, a.gcd(&b));
+        assert_eq!(s * a.clone() + t * b, g);
+
+        let untrimmed_zero = FFPoly::new_no_trim(vec![FFieldUnit::zero(); 3], "x");
+        let (g, s, t) = a.xgcd(&untrimmed_zero);
+        assert_eq!(g, a.monic());
+        assert_eq!(s * a + t * untrimmed_zero, g);
+    }
+
+    #[test]
+    fn test_inverse_mod() {
+        let m = from_roots(&[1, 2, 3, 4]);
+        let f = get_instance_in_range(10, 20);
+        let inv = f.inverse_mod(&m).unwrap();
+        assert!(inv.degree() < m.degree());
+        assert_eq!(
+            (f * inv).qdiv(&m).1,
+            FFPoly::new(vec![FFieldUnit::one()], "x")
+        );
+
+        assert_eq!(
+            from_roots(&[2, 7]).inverse_mod(&m),
+            Err(Error::NotInvertible)
+        );
+        assert_eq!(
+            m.inverse_mod(&FFPoly::zero("x")),
+            Err(Error::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_crt() {
+        let moduli = vec![
+            from_roots(&[1, 2]),
+            from_roots(&[3]),
+            from_roots(&[4, 5, 6]),
+        ];
+        let x = get_instance_in_range(-7, -1);
+        let residues: Vec<FFPoly> = moduli.iter().map(|m| x.qdiv(m).1).collect();
+        assert_eq!(crt(&residues, &moduli, "x"), Ok(x));
+
+        let not_coprime = vec![from_roots(&[1, 2]), from_roots(&[2, 3])];
+        assert_eq!(
+            crt(&residues[..2], &not_coprime, "x"),
+            Err(Error::NotInvertible)
+        );
+    }
+
+    #[test]
+    fn test_derivative() {
+        let p = get_instance_in_range(1, 5);
+        assert_eq!(
+            p.derivative().coeffs,
+            vec![FFieldUnit::new(2), FFieldUnit::new(6), FFieldUnit::new(12)]
+        );
+        assert_eq!(
+            FFPoly::new(vec![FFieldUnit::new(7)], "x").derivative(),
+            FFPoly::zero("x")
+        );
+    }
+
+    #[test]
+    fn test_multi_eval_strategies() {
+        let f = get_instance_in_range(-200, 200);
+        let subgroup = crate::domain::Domain::<FFieldUnit>::subgroup(8)
+            .unwrap()
+            .elements();
+        let arbitrary = get_ffunits_in_range(1000, 1300);
+        for points in [subgroup, arbitrary, get_ffunits_in_range(5, 10)] {
+            let naive: Vec<FFieldUnit> = points.iter().map(|x| f.eval(x)).collect();
+            assert_eq!(f.multi_eval(&points), naive);
+        }
+
+        // half of the points are roots, enough of both to go through the subproduct tree
+        let points = get_ffunits_in_range(1000, 1300);
+        let linear: Vec<FFPoly> = points[..150]
+            .iter()
+            .map(|x| FFPoly::gen_linear_term(x, "x"))
+            .collect();
+        let f = prod(&linear, "x");
+        let vals = f.multi_eval(&points);
+        assert!(vals[..150].iter().all(|v| v.is_zero()));
+        assert_eq!(vals, points.iter().map(|x| f.eval(x)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_interpolation_at_arbitrary_points_matches_lagrange() {
+        let xs = get_ffunits_in_range(-20, 13);
+        let ys = get_ffunits_in_range(50, 83);
+        let f = interpolate_poly(&xs, &ys, "x");
+        assert_eq!(
+            f,
+            interpolate_lang_poly(&ys, &calc_langrange_polys(&xs, "x"), "x")
+        );
+    }
+
+    #[test]
+    fn test_interpolation_over_subgroup_matches_lagrange() {
+        let xs = crate::domain::Domain::<FFieldUnit>::subgroup(4)
+            .unwrap()
+            .elements();
+        let ys = get_ffunits_in_range(30, 46);
+        let f = interpolate_poly(&xs, &ys, "x");
+        let lang = interpolate_lang_poly(&ys, &calc_langrange_polys(&xs, "x"), "x");
+        assert_eq!(f, lang);
+        for (x, y) in xs.iter().zip(ys.iter()) {
+            assert_eq!(f.eval(x), *y);
+        }
+    }
+
     // #[test]
     // fn test_prod() {
     //     let ffunits = get_instance_in_range(2, 5);
@@ -476,4 +1339,155 @@ mod tests {
         let prd = prod_ffunits(&ffunits);
         assert_eq!(prd, FFieldUnit::new(24));
     }
+
+    #[test]
+    fn test_binary_encoding_round_trip() {
+        let p = get_instance_in_range(-3, 7);
+        let bytes = p.to_bytes();
+        assert_eq!(bytes.len(), 4 + 10 * 4);
+        assert_eq!(&bytes[..4], &[10, 0, 0, 0]);
+        assert_eq!(FFPoly::from_bytes(&bytes, "x"), Ok(p));
+
+        let empty: FFPoly = FFPoly::new(vec![], "x");
+        assert_eq!(FFPoly::from_bytes(&empty.to_bytes(), "x"), Ok(empty));
+
+        // trimmed on construction, so even zeros handed to new never reach the encoding
+        let p = FFPoly::new(get_ffunits_in_range(-3, 1), "x");
+        assert_eq!(p.coeffs.len(), 3);
+        assert_eq!(FFPoly::from_bytes(&p.to_bytes(), "x"), Ok(p));
+
+        // untrimmed polynomials encode as their trimmed form
+        let one = FFieldUnit::one();
+        let untrimmed = FFPoly::new_no_trim(vec![one, FFieldUnit::zero()], "x");
+        assert_eq!(
+            FFPoly::from_bytes(&untrimmed.to_bytes(), "x"),
+            Ok(FFPoly::new(vec![one], "x"))
+        );
+        let all_zero = FFPoly::<FFieldUnit>::new_no_trim(vec![FFieldUnit::zero(); 3], "x");
+        assert_eq!(all_zero.to_bytes(), vec![0, 0, 0, 0]);
+        assert_eq!(
+            FFPoly::from_bytes(&all_zero.to_bytes(), "x"),
+            Ok(FFPoly::<FFieldUnit>::zero("x"))
+        );
+    }
+
+    #[test]
+    fn test_binary_encoding_rejects_bad_input() {
+        let p = get_instance_in_range(1, 4);
+        let mut bytes = p.to_bytes();
+        assert_eq!(
+            FFPoly::<FFieldUnit>::from_bytes(&bytes[..bytes.len() - 1], "x"),
+            Err(Error::InvalidLength)
+        );
+        assert_eq!(
+            FFPoly::<FFieldUnit>::from_bytes(&bytes[..2], "x"),
+            Err(Error::InvalidLength)
+        );
+        // a zero leading coefficient is a second encoding of a shorter polynomial
+        let mut padded = bytes.clone();
+        padded[0] += 1;
+        padded.extend([0; 4]);
+        assert_eq!(
+            FFPoly::<FFieldUnit>::from_bytes(&padded, "x"),
+            Err(Error::NonCanonical)
+        );
+        assert_eq!(
+            FFPoly::<FFieldUnit>::from_bytes(&[1, 0, 0, 0, 0, 0, 0, 0], "x"),
+            Err(Error::NonCanonical)
+        );
+        // last coefficient set to p
+        let n = bytes.len();
+        bytes[n - 4..].copy_from_slice(&[1, 0, 0, 0xc0]);
+        assert_eq!(
+            FFPoly::<FFieldUnit>::from_bytes(&bytes, "x"),
+            Err(Error::NonCanonical)
+        );
+    }
+
+    #[test]
+    fn test_string_round_trip() {
+        let p = FFPoly::new(get_ffunits_in_range(-1, 3), "y");
+        let s = p.to_string();
+        assert_eq!(s, "2*y^3 + 1*y^2 + 3221225472");
+        assert_eq!(s.parse::<FFPoly>(), Ok(p));
+
+        let q = get_instance_in_range(-20, 20);
+        assert_eq!(q.to_string().parse::<FFPoly>(), Ok(q));
+        let zero: FFPoly = FFPoly::zero("x");
+        assert_eq!(zero.to_string(), "0");
+        assert_eq!("0".parse::<FFPoly>(), Ok(zero));
+
+        // any order, hex coefficients and repeated degrees are accepted
+        assert_eq!(
+            "1*x + 0x10 + 2*x^2 + 3*x".parse::<FFPoly>(),
+            Ok(FFPoly::new(
+                vec![FFieldUnit::new(16), FFieldUnit::new(4), FFieldUnit::new(2)],
+                "x"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_string_parsing_rejects_bad_input() {
+        for s in ["", "1 +", "1*x + 2*y", "1*", "1*x^", "1*x^-1", "x^2", "p*x"] {
+            assert_eq!(s.parse::<FFPoly>(), Err(Error::InvalidDigit), "{}", s);
+        }
+        assert_eq!("3221225473*x".parse::<FFPoly>(), Err(Error::NonCanonical));
+    }
+
+    #[test]
+    fn test_num_traits() {
+        let p = get_instance_in_range(1, 4);
+        assert!(<FFPoly as Zero>::zero().is_zero());
+        assert!(!p.is_zero());
+        assert_eq!(p.clone() * <FFPoly as One>::one(), p);
+        assert_eq!(Pow::pow(p.clone(), 3u32), p.clone() * p.clone() * p.clone());
+        assert_eq!(Pow::pow(p.clone(), 0u32), <FFPoly as One>::one());
+    }
+
+    #[test]
+    fn test_operator_set() {
+        let p = get_instance_in_range(1, 4);
+        let q = get_instance_in_range(-2, 5);
+        assert!((-p.clone() + p.clone()).is_zero());
+
+        let mut r = p.clone();
+        r += q.clone();
+        r *= q.clone();
+        r -= p.clone();
+        assert_eq!(r, (p.clone() + q.clone()) * q.clone() - p.clone());
+
+        let (quot, rem) = r.qdiv(&p);
+        assert_eq!(r.clone() % p.clone(), rem);
+        let mut s = r.clone();
+        s %= p.clone();
+        assert_eq!(s, rem);
+        let mut s = r - rem;
+        s /= p.clone();
+        assert_eq!(s, quot);
+        assert_eq!(Pow::pow(p.clone(), 5u64), Pow::pow(p.clone(), 5u32));
+
+        let polys = vec![p.clone(), q.clone(), p.clone()];
+        assert_eq!(
+            polys.iter().cloned().sum::<FFPoly>(),
+            p.clone() + q.clone() + p.clone()
+        );
+        assert_eq!(polys.into_iter().product::<FFPoly>(), p.clone() * q * p);
+    }
+
+    #[test]
+    fn test_subtraction_with_longer_right_operand() {
+        let one = FFieldUnit::one();
+        let p = FFPoly::new(vec![one, one], "x");
+        let q = FFPoly::monomial(2, one, "x");
+        // (x + 1) - x^2 = -x^2 + x + 1
+        let expected = FFPoly::new(vec![one, one, one.neg()], "x");
+        assert_eq!(p.clone() - q.clone(), expected);
+        assert_eq!(q.clone() - p.clone(), -expected.clone());
+
+        let mut r = p.clone();
+        r -= q;
+        assert_eq!(r, expected);
+        assert_eq!(FFPoly::new(vec![], "x") - p.clone(), -p);
+    }
 }
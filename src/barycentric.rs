@@ -0,0 +1,120 @@
+use crate::domain::Domain;
+use crate::field::{batch_inverse_in_place, Field};
+use crate::subproduct::SubproductTree;
+
+/// evaluates the polynomial of degree < n through (x_j, y_j) at any point without
+/// interpolating it, using the barycentric formula
+/// p(z) = (sum_j w_j y_j / (z - x_j)) / (sum_j w_j / (z - x_j)) with weights
+/// w_j = 1 / prod_{i != j} (x_j - x_i). the weights depend only on the x-values and
+/// are computed once; every evaluation is then O(n) plus one inversion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Barycentric<F: Field> {
+    x_vals: Vec<F>,
+    weights: Vec<F>,
+}
+
+impl<F: Field> Barycentric<F> {
+    /// weights for an arbitrary set of distinct x-values, read off a subproduct tree
+    /// in O(M(n) log n). panics on a repeated x-value.
+    pub fn new(x_vals: &[F]) -> Self {
+        Self {
+            x_vals: x_vals.to_vec(),
+            weights: SubproductTree::new(x_vals, "x").weights(),
+        }
+    }
+
+    /// weights for a subgroup or coset in O(n). the domain vanishes on x^n - c, so
+    /// prod_{i != j} (x_j - x_i) = n x_j^(n - 1) = n c / x_j and w_j = x_j / (n c).
+    pub fn from_domain(domain: &Domain<F>) -> Self {
+        let x_vals = domain.elements();
+        let n = domain.size();
+        let c = domain.offset().pow(n as u64);
+        let scale = (F::from_u128(n as u128) * c).inverse();
+        let weights = x_vals.iter().map(|x| *x * scale).collect();
+        Self { x_vals, weights }
+    }
+
+    pub fn x_vals(&self) -> &[F] {
+        &self.x_vals
+    }
+
+    pub fn weights(&self) -> &[F] {
+        &self.weights
+    }
+
+    /// value at z of the polynomial taking y_vals[j] at x_vals[j].
+    pub fn evaluate(&self, y_vals: &[F], z: &F) -> F {
+        assert_eq!(y_vals.len(), self.x_vals.len(), "one value per x");
+        if let Some(j) = self.x_vals.iter().position(|x| x == z) {
+            return y_vals[j];
+        }
+
+        let mut diffs: Vec<F> = self.x_vals.iter().map(|x| *z - *x).collect();
+        batch_inverse_in_place(&mut diffs);
+        let (mut numer, mut denom) = (F::zero(), F::zero());
+        for ((w, y), d) in self.weights.iter().zip(y_vals).zip(diffs) {
+            let t = *w * d;
+            numer = numer + t * *y;
+            denom = denom + t;
+        }
+        numer / denom
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffield_unit::FFieldUnit;
+    use crate::utils::get_ffunits_in_range;
+    use crate::FFPoly::{interpolate_poly, FFPoly};
+
+    #[test]
+    fn test_arbitrary_points() {
+        let xs = get_ffunits_in_range(-5, 12);
+        let f = FFPoly::new(get_ffunits_in_range(40, 57), "x");
+        let ys = f.eval_batch(&xs);
+        let bary = Barycentric::new(&xs);
+        for z in [FFieldUnit::new(1000), FFieldUnit::new(-77), xs[3]] {
+            assert_eq!(bary.evaluate(&ys, &z), f.eval(&z));
+        }
+        let single = Barycentric::new(&[FFieldUnit::new(4)]);
+        assert_eq!(
+            single.evaluate(&[FFieldUnit::new(9)], &FFieldUnit::new(1)),
+            9
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "interpolation points must be distinct")]
+    fn test_rejects_repeated_points() {
+        let xs = get_ffunits_in_range(-5, 12);
+        Barycentric::new(&[xs.clone(), vec![xs[7]]].concat());
+    }
+
+    #[test]
+    fn test_domain_weights_match_generic_weights() {
+        let dom = Domain::<FFieldUnit>::coset(5, FFieldUnit::ffgenerator()).unwrap();
+        assert_eq!(
+            Barycentric::from_domain(&dom),
+            Barycentric::new(&dom.elements())
+        );
+        let sub = Domain::<FFieldUnit>::subgroup_of_size(48).unwrap();
+        assert_eq!(
+            Barycentric::from_domain(&sub),
+            Barycentric::new(&sub.elements())
+        );
+    }
+
+    #[test]
+    fn test_out_of_domain_evaluation() {
+        // the trace polynomial's value outside its subgroup, without interpolating it
+        let dom = Domain::<FFieldUnit>::subgroup(6).unwrap();
+        let trace = get_ffunits_in_range(1000, 1064);
+        let z = FFieldUnit::new(31337);
+        let f = interpolate_poly(&dom.elements(), &trace, "x");
+        assert_eq!(
+            Barycentric::from_domain(&dom).evaluate(&trace, &z),
+            f.eval(&z)
+        );
+    }
+}
@@ -1,7 +1,13 @@
+use crate::extension::ExtensionField;
 use crate::ffield_unit::FFieldUnit;
-use crate::utils::hash256_str;
+use crate::field::Field;
+use crate::utils::{hash256_str, hash256_vec, u8_to_hexstr};
 
-pub fn serialize(units: &[FFieldUnit]) -> String {
+/// the state keeps 31 hex digits of a digest, so integers drawn from it have 124 bits.
+/// fields with wider canonical encodings draw from the full 256-bit digest instead.
+const MAX_STATE_DRAW_BYTES: usize = 15;
+
+pub fn serialize<F: Field>(units: &[F]) -> String {
     units
         .iter()
         .map(|unit| unit.to_string())
@@ -9,6 +15,11 @@ pub fn serialize(units: &[FFieldUnit]) -> String {
         .join(",")
 }
 
+/// fixed-width canonical encoding of units, independent of decimal formatting.
+pub fn serialize_bytes<F: Field>(units: &[F]) -> Vec<u8> {
+    units.iter().flat_map(|u| u.to_canonical_bytes()).collect()
+}
+
 /// a channel can be used by a prover or a verifier to preseerve the semantics of an
 /// interactive proof system, while under the hood its infact non-interactive, and
 /// uses Sha256 to generate randomness when this is required.
@@ -38,9 +49,8 @@ impl Channel {
     pub fn receive_rnd_int(&mut self, min: &FFieldUnit, max: &i128, show_in_proof: bool) -> i128 {
         let min = min.inner();
         // let max = max.inner();
-        dbg!(&self.state);
         let num = min + (i128::from_str_radix(&self.state, 16).unwrap() % (max - min + 1));
-        let hash = hash256_str(&self.state.as_bytes());
+        let hash = hash256_str(self.state.as_bytes());
         self.state = String::from(&hash[..(hash.len() / 2) - 1]);
         if show_in_proof {
             self.proof.push_str(&format!("{}", num));
@@ -49,8 +59,80 @@ impl Channel {
     }
 
     pub fn receive_rnd_ffunit(&mut self) -> FFieldUnit {
-        let num = self.receive_rnd_int(&FFieldUnit::zero(), &FFieldUnit::modulo_prime(), false);
-        self.proof.push_str(&format!("{:?}", num));
-        return FFieldUnit::new(num);
+        self.receive_rnd_field()
+    }
+
+    /// draws a pseudo-random element of any field F from the current state. small
+    /// fields reduce the 124-bit state itself, which keeps their transcripts as they
+    /// always were; wider fields such as Stark252 reduce the 256-bit digest of the
+    /// state so that every element can be drawn.
+    pub fn receive_rnd_field<F: Field>(&mut self) -> F {
+        let digest = hash256_vec(self.state.as_bytes());
+        let unit = if F::NUM_BYTES <= MAX_STATE_DRAW_BYTES {
+            F::from_random_u128(u128::from_str_radix(&self.state, 16).unwrap())
+        } else {
+            F::from_random_digest(&digest)
+        };
+        let hash = u8_to_hexstr(&digest);
+        self.state = String::from(&hash[..(hash.len() / 2) - 1]);
+        self.proof.push_str(&unit.to_string());
+        unit
+    }
+
+    /// draws an extension field element as DEGREE independent base field draws,
+    /// so challenges get the full soundness of the extension.
+    pub fn receive_rnd_ext<E: ExtensionField>(&mut self) -> E {
+        let coeffs: Vec<E::Base> = (0..E::DEGREE).map(|_| self.receive_rnd_field()).collect();
+        E::from_base_slice(&coeffs)
+    }
+}
+
+impl Default for Channel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stark252::Stark252;
+
+    #[test]
+    fn test_receive_rnd_ffunit_matches_int_draw() {
+        let mut c1 = Channel::new();
+        let mut c2 = Channel::new();
+        c1.send("abc");
+        c2.send("abc");
+        for _ in 0..4 {
+            let n = c1.receive_rnd_int(&FFieldUnit::zero(), &FFieldUnit::modulo_prime(), false);
+            assert_eq!(FFieldUnit::new(n), c2.receive_rnd_ffunit());
+        }
+    }
+
+    #[test]
+    fn test_wide_field_draws_use_the_full_digest() {
+        let mut c = Channel::new();
+        c.send("abc");
+        let draws: Vec<Stark252> = (0..4).map(|_| c.receive_rnd_field()).collect();
+        // a draw from the 124-bit state would leave the top bytes zero
+        assert!(draws
+            .iter()
+            .any(|x| x.to_canonical_bytes()[16..].iter().any(|b| *b != 0)));
+
+        let mut c = Channel::new();
+        c.send("abc");
+        let digest = hash256_vec(c.state.as_bytes());
+        assert_eq!(
+            c.receive_rnd_field::<Stark252>(),
+            Stark252::from_random_digest(&digest)
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        let units = vec![FFieldUnit::new(1), FFieldUnit::new(-1)];
+        assert_eq!(serialize(&units), "1,3221225472");
+        assert_eq!(serialize_bytes(&units), vec![1, 0, 0, 0, 0, 0, 0, 0xc0]);
     }
 }
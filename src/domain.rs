@@ -0,0 +1,177 @@
+use crate::field::Field;
+
+/// the coset offset * <generator> of a multiplicative subgroup, usually of power-of-two
+/// size. a plain subgroup is the coset with offset one.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Domain<F: Field> {
+    size: usize,
+    generator: F,
+    offset: F,
+}
+
+impl<F: Field> Domain<F> {
+    /// the subgroup of order 2^log_n, None if the field has no such subgroup.
+    pub fn subgroup(log_n: u32) -> Option<Self> {
+        Self::coset(log_n, F::one())
+    }
+
+    /// offset * H where H is the subgroup of order 2^log_n.
+    pub fn coset(log_n: u32, offset: F) -> Option<Self> {
+        Some(Self {
+            size: 1usize.checked_shl(log_n)?,
+            generator: F::root_of_unity(log_n)?,
+            offset,
+        })
+    }
+
+    /// the subgroup of order n, e.g. 3 * 2^k points. None if the field has no such
+    /// subgroup.
+    pub fn subgroup_of_size(n: usize) -> Option<Self> {
+        Self::coset_of_size(n, F::one())
+    }
+
+    /// offset * H where H is the subgroup of order n.
+    pub fn coset_of_size(n: usize, offset: F) -> Option<Self> {
+        Some(Self {
+            size: n,
+            generator: F::root_of_unity_of_order(n as u64)?,
+            offset,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// log2 of the power-of-two part of the size; the exact log2 for radix-2 domains.
+    pub fn log_size(&self) -> u32 {
+        self.size.trailing_zeros()
+    }
+
+    pub fn generator(&self) -> F {
+        self.generator
+    }
+
+    pub fn offset(&self) -> F {
+        self.offset
+    }
+
+    pub fn is_subgroup(&self) -> bool {
+        self.offset == F::one()
+    }
+
+    /// offset * generator^i
+    pub fn element(&self, i: usize) -> F {
+        self.offset * self.generator.pow(i as u64)
+    }
+
+    pub fn iter(&self) -> DomainIter<F> {
+        DomainIter {
+            cur: self.offset,
+            generator: self.generator,
+            remaining: self.size(),
+        }
+    }
+
+    pub fn elements(&self) -> Vec<F> {
+        self.iter().collect()
+    }
+}
+
+impl<F: Field> IntoIterator for &Domain<F> {
+    type Item = F;
+    type IntoIter = DomainIter<F>;
+
+    fn into_iter(self) -> DomainIter<F> {
+        self.iter()
+    }
+}
+
+/// walks offset, offset * g, offset * g^2, ... one multiplication per element.
+#[derive(Debug, Clone)]
+pub struct DomainIter<F: Field> {
+    cur: F,
+    generator: F,
+    remaining: usize,
+}
+
+impl<F: Field> Iterator for DomainIter<F> {
+    type Item = F;
+
+    fn next(&mut self) -> Option<F> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let res = self.cur;
+        self.cur = self.cur * self.generator;
+        self.remaining -= 1;
+        Some(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<F: Field> ExactSizeIterator for DomainIter<F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffield_unit::FFieldUnit;
+    use crate::goldilocks::Goldilocks;
+
+    #[test]
+    fn test_subgroup_matches_generator_powers() {
+        let g = FFieldUnit::generator();
+        let dom = Domain::<FFieldUnit>::subgroup(10).unwrap();
+        assert_eq!(dom.generator(), g);
+        assert_eq!(dom.size(), 1024);
+        let elems = dom.elements();
+        assert_eq!(elems.len(), 1024);
+        for (i, e) in elems.iter().enumerate().step_by(97) {
+            assert_eq!(*e, FFieldUnit::pow(g, i as u32));
+        }
+        // the walk closes up: g^1024 = 1
+        assert_eq!(elems[1023] * g, FFieldUnit::one());
+    }
+
+    #[test]
+    fn test_coset() {
+        let w = FFieldUnit::ffgenerator();
+        let dom = Domain::coset(13, w).unwrap();
+        assert_eq!(dom.generator(), FFieldUnit::new(1734477367));
+        assert!(!dom.is_subgroup());
+        let elems: Vec<FFieldUnit> = dom.iter().collect();
+        assert_eq!(elems.len(), 8192);
+        assert_eq!(elems[0], w);
+        assert_eq!(elems[5], dom.element(5));
+        assert_eq!(elems[8191], dom.element(8191));
+    }
+
+    #[test]
+    fn test_mixed_radix_sizes() {
+        let dom = Domain::<FFieldUnit>::subgroup_of_size(3072).unwrap();
+        assert_eq!(dom.size(), 3072);
+        assert_eq!(dom.log_size(), 10);
+        let g = dom.generator();
+        assert!(g.is_primitive_nth_root_of_unity(3072));
+        assert_eq!(dom.elements()[3071] * g, FFieldUnit::one());
+        // powers of two agree with the radix-2 constructor
+        assert_eq!(
+            Domain::<FFieldUnit>::subgroup_of_size(1024),
+            Domain::subgroup(10)
+        );
+        assert!(Domain::<FFieldUnit>::subgroup_of_size(5 * 1024).is_none());
+    }
+
+    #[test]
+    fn test_sizes_beyond_two_adicity() {
+        assert!(Domain::<FFieldUnit>::subgroup(31).is_none());
+        assert_eq!(Domain::<Goldilocks>::subgroup(32).unwrap().log_size(), 32);
+        assert_eq!(
+            Domain::<FFieldUnit>::subgroup(0).unwrap().elements().len(),
+            1
+        );
+    }
+}
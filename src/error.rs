@@ -0,0 +1,33 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// input is empty or contains a character that is not a digit in its radix
+    InvalidDigit,
+    /// value is not below the field modulus, or a polynomial encoding ends in a zero
+    /// coefficient
+    NonCanonical,
+    /// byte input is not of the expected size
+    InvalidLength,
+    /// divisor is zero
+    DivisionByZero,
+    /// value has no inverse, e.g. a polynomial sharing a factor with the modulus
+    NotInvertible,
+    /// dividend is not a multiple of the divisor
+    NotDivisible,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidDigit => write!(f, "invalid digit"),
+            Error::NonCanonical => write!(f, "non-canonical encoding"),
+            Error::InvalidLength => write!(f, "invalid byte length"),
+            Error::DivisionByZero => write!(f, "division by zero"),
+            Error::NotInvertible => write!(f, "value is not invertible"),
+            Error::NotDivisible => write!(f, "not divisible"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,218 @@
+use crate::domain::Domain;
+use crate::ffield_unit::FFieldUnit;
+use crate::field::{batch_inverse_in_place, Field};
+use crate::ntt::bit_reverse_permute;
+use crate::FFPoly::FFPoly;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// storage order of the values in an Evaluations.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Order {
+    /// values[i] is the value at domain.element(i).
+    Natural,
+    /// values[i] is the value at domain.element(bitrev(i)), the layout FRI folding
+    /// and Merkle openings of sibling points want. power-of-two domains only.
+    BitReversed,
+}
+
+/// a polynomial in evaluation form: its values on every point of a domain.
+/// arithmetic is pointwise, so it only agrees with coefficient-form arithmetic while
+/// the result has degree < domain.size().
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluations<F: Field = FFieldUnit> {
+    domain: Domain<F>,
+    values: Vec<F>,
+    order: Order,
+}
+
+impl<F: Field> Evaluations<F> {
+    /// values in natural order.
+    pub fn new(domain: Domain<F>, values: Vec<F>) -> Self {
+        assert_eq!(values.len(), domain.size(), "one value per domain point");
+        Self {
+            domain,
+            values,
+            order: Order::Natural,
+        }
+    }
+
+    pub fn from_poly(poly: &FFPoly<F>, domain: Domain<F>) -> Self {
+        Self::new(domain, poly.evaluate_over(&domain))
+    }
+
+    /// the unique polynomial of degree < domain.size() with these values.
+    pub fn to_poly(&self, var: &str) -> FFPoly<F> {
+        let natural = self.clone().into_order(Order::Natural);
+        FFPoly::interpolate_over(&natural.domain, &natural.values, var)
+    }
+
+    pub fn domain(&self) -> &Domain<F> {
+        &self.domain
+    }
+
+    pub fn values(&self) -> &[F] {
+        &self.values
+    }
+
+    pub fn order(&self) -> Order {
+        self.order
+    }
+
+    pub fn into_values(self) -> Vec<F> {
+        self.values
+    }
+
+    /// reorders the values, a no-op when already in the requested order.
+    pub fn into_order(mut self, order: Order) -> Self {
+        if order != self.order {
+            bit_reverse_permute(&mut self.values);
+            self.order = order;
+        }
+        self
+    }
+
+    /// value at domain.element(i), whatever the storage order.
+    pub fn value_at(&self, i: usize) -> F {
+        match self.order {
+            Order::Natural => self.values[i],
+            Order::BitReversed => {
+                let log_n = self.domain.log_size();
+                let j = if log_n == 0 {
+                    0
+                } else {
+                    i.reverse_bits() >> (usize::BITS - log_n)
+                };
+                self.values[j]
+            }
+        }
+    }
+
+    pub fn scalar_mul(&self, v: &F) -> Self {
+        let values = self.values.iter().map(|x| *x * *v).collect();
+        Self { values, ..*self }
+    }
+
+    /// applies op pointwise, both sides must live on the same domain in the same order.
+    fn zip_with(self, other: Self, op: impl Fn(F, F) -> F) -> Self {
+        assert_eq!(
+            self.domain, other.domain,
+            "evaluations on different domains"
+        );
+        assert_eq!(self.order, other.order, "evaluations in different orders");
+        let values = self
+            .values
+            .iter()
+            .zip(other.values)
+            .map(|(a, b)| op(*a, b))
+            .collect();
+        Self { values, ..self }
+    }
+}
+
+impl<F: Field> Add for Evaluations<F> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a + b)
+    }
+}
+
+impl<F: Field> Sub for Evaluations<F> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a - b)
+    }
+}
+
+impl<F: Field> Mul for Evaluations<F> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a * b)
+    }
+}
+
+impl<F: Field> Mul<F> for Evaluations<F> {
+    type Output = Self;
+
+    fn mul(self, scalar: F) -> Self {
+        self.scalar_mul(&scalar)
+    }
+}
+
+impl<F: Field> Div for Evaluations<F> {
+    type Output = Self;
+
+    /// one batch inversion for the whole domain. panics if other is zero anywhere.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, mut other: Self) -> Self {
+        assert!(
+            other.values.iter().all(|v| !v.is_zero()),
+            "division by 0 on the domain!"
+        );
+        batch_inverse_in_place(&mut other.values);
+        self * other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::get_ffunits_in_range;
+
+    fn coset() -> Domain<FFieldUnit> {
+        Domain::coset(5, FFieldUnit::ffgenerator()).unwrap()
+    }
+
+    #[test]
+    fn test_poly_round_trip() {
+        let f = FFPoly::new(get_ffunits_in_range(-10, 20), "x");
+        let evals = Evaluations::from_poly(&f, coset());
+        assert_eq!(evals.values(), &f.eval_batch(&coset().elements())[..]);
+        assert_eq!(evals.to_poly("x"), f);
+    }
+
+    #[test]
+    fn test_pointwise_arithmetic_matches_coefficients() {
+        let f = FFPoly::new(get_ffunits_in_range(1, 15), "x");
+        let g = FFPoly::new(get_ffunits_in_range(-4, 10), "x");
+        let (ef, eg) = (
+            Evaluations::from_poly(&f, coset()),
+            Evaluations::from_poly(&g, coset()),
+        );
+        let c = FFieldUnit::new(3);
+        assert_eq!(
+            (ef.clone() + eg.clone()).to_poly("x"),
+            f.clone() + g.clone()
+        );
+        assert_eq!(
+            (ef.clone() - eg.clone()).to_poly("x"),
+            f.clone() - g.clone()
+        );
+        assert_eq!(
+            (ef.clone() * eg.clone()).to_poly("x"),
+            f.clone() * g.clone()
+        );
+        assert_eq!((ef.clone() * c).to_poly("x"), f.clone() * c);
+
+        // exact quotient: (f * g) / g = f; the coset avoids the roots of g
+        let fg = ef.clone() * eg.clone();
+        assert_eq!((fg / eg).to_poly("x"), f);
+    }
+
+    #[test]
+    fn test_bit_reversed_order() {
+        let f = FFPoly::new(get_ffunits_in_range(5, 25), "x");
+        let natural = Evaluations::from_poly(&f, coset());
+        let reversed = natural.clone().into_order(Order::BitReversed);
+        assert_eq!(reversed.order(), Order::BitReversed);
+        assert_eq!(reversed.values()[1], natural.values()[16]);
+        for i in 0..32 {
+            assert_eq!(reversed.value_at(i), natural.value_at(i));
+        }
+        let doubled = reversed.clone() + reversed.clone();
+        assert_eq!(doubled.to_poly("x"), f.clone() + f.clone());
+        assert_eq!(reversed.into_order(Order::Natural), natural);
+    }
+}
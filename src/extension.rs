@@ -0,0 +1,422 @@
+use crate::error::Error;
+use crate::ffield_unit::FFieldUnit;
+use crate::field::Field;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// a finite extension of a base field, elements are written as polynomials of
+/// degree < DEGREE in a fixed root u of the defining irreducible polynomial.
+pub trait ExtensionField: Field {
+    type Base: Field;
+    const DEGREE: usize;
+
+    fn from_base(b: Self::Base) -> Self;
+
+    /// coefficients of 1, u, u^2, ... missing ones are zero.
+    fn from_base_slice(coeffs: &[Self::Base]) -> Self;
+
+    fn to_base_coeffs(&self) -> Vec<Self::Base>;
+
+    /// x -> x^p, where p is the order of the base field.
+    fn frobenius(&self) -> Self;
+}
+
+/// non-residue used by both extensions. 5 generates the multiplicative group of
+/// FFieldUnit, so it is neither a square nor a cube and x^2 - 5, x^3 - 5 are irreducible.
+fn nonresidue() -> FFieldUnit {
+    FFieldUnit::ffgenerator()
+}
+
+fn base_at(coeffs: &[FFieldUnit], i: usize) -> FFieldUnit {
+    coeffs.get(i).copied().unwrap_or(FFieldUnit::zero())
+}
+
+/// degree coefficients drawn from a random integer as its lowest base-p digits, so a
+/// channel draw fills the whole extension and not only its base field.
+fn base_coeffs_from_random_u128(mut n: u128, degree: usize) -> Vec<FFieldUnit> {
+    let p = FFieldUnit::modulo_prime() as u128;
+    (0..degree)
+        .map(|_| {
+            let c = FFieldUnit::from_u128(n % p);
+            n /= p;
+            c
+        })
+        .collect()
+}
+
+/// degree coefficients, each reduced from its own share of the digest.
+fn base_coeffs_from_random_digest(digest: &[u8], degree: usize) -> Vec<FFieldUnit> {
+    let share = digest.len().div_ceil(degree).max(1);
+    let mut coeffs: Vec<FFieldUnit> = digest
+        .chunks(share)
+        .map(<FFieldUnit as Field>::from_random_digest)
+        .collect();
+    coeffs.resize(degree, FFieldUnit::zero());
+    coeffs
+}
+
+fn base_coeffs_from_bytes(bytes: &[u8], degree: usize) -> Result<Vec<FFieldUnit>, Error> {
+    if bytes.len() != degree * FFieldUnit::NUM_BYTES {
+        return Err(Error::InvalidLength);
+    }
+    bytes
+        .chunks_exact(FFieldUnit::NUM_BYTES)
+        .map(FFieldUnit::from_bytes)
+        .collect()
+}
+
+/// FFieldUnit[u] / (u^2 - 5), about 62 bits.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct QuadExt(pub [FFieldUnit; 2]);
+
+impl QuadExt {
+    pub fn new(a0: FFieldUnit, a1: FFieldUnit) -> Self {
+        Self([a0, a1])
+    }
+
+    /// a0^2 - 5 * a1^2, the product of x and its conjugate.
+    pub fn norm(&self) -> FFieldUnit {
+        let [a0, a1] = self.0;
+        a0 * a0 - nonresidue() * a1 * a1
+    }
+
+    pub fn inverse(&self) -> Self {
+        let n_inv = self.norm().inverse();
+        let [a0, a1] = self.0;
+        Self([a0 * n_inv, FFieldUnit::zero() - a1 * n_inv])
+    }
+}
+
+impl Add for QuadExt {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self([self.0[0] + other.0[0], self.0[1] + other.0[1]])
+    }
+}
+
+impl Sub for QuadExt {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self([self.0[0] - other.0[0], self.0[1] - other.0[1]])
+    }
+}
+
+impl Mul for QuadExt {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let ([a0, a1], [b0, b1]) = (self.0, other.0);
+        Self([a0 * b0 + nonresidue() * a1 * b1, a0 * b1 + a1 * b0])
+    }
+}
+
+impl Mul<FFieldUnit> for QuadExt {
+    type Output = Self;
+    fn mul(self, other: FFieldUnit) -> Self {
+        Self([self.0[0] * other, self.0[1] * other])
+    }
+}
+
+impl Div for QuadExt {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inverse()
+    }
+}
+
+impl fmt::Display for QuadExt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.0[0], self.0[1])
+    }
+}
+
+impl Field for QuadExt {
+    const TWO_ADICITY: u32 = FFieldUnit::TWO_ADICITY;
+    const NUM_BYTES: usize = 2 * FFieldUnit::NUM_BYTES;
+
+    fn zero() -> Self {
+        Self::from_base(FFieldUnit::zero())
+    }
+
+    fn one() -> Self {
+        Self::from_base(FFieldUnit::one())
+    }
+
+    fn from_u128(n: u128) -> Self {
+        Self::from_base(FFieldUnit::from_u128(n))
+    }
+
+    fn inverse(&self) -> Self {
+        self.inverse()
+    }
+
+    fn two_adic_generator() -> Self {
+        Self::from_base(FFieldUnit::two_adic_generator())
+    }
+
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        self.0.iter().flat_map(|c| c.to_canonical_bytes()).collect()
+    }
+
+    fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        base_coeffs_from_bytes(bytes, Self::DEGREE).map(|c| Self::from_base_slice(&c))
+    }
+
+    fn from_random_u128(n: u128) -> Self {
+        Self::from_base_slice(&base_coeffs_from_random_u128(n, Self::DEGREE))
+    }
+
+    fn from_random_digest(digest: &[u8]) -> Self {
+        Self::from_base_slice(&base_coeffs_from_random_digest(digest, Self::DEGREE))
+    }
+}
+
+impl ExtensionField for QuadExt {
+    type Base = FFieldUnit;
+    const DEGREE: usize = 2;
+
+    fn from_base(b: FFieldUnit) -> Self {
+        Self([b, FFieldUnit::zero()])
+    }
+
+    fn from_base_slice(coeffs: &[FFieldUnit]) -> Self {
+        Self([base_at(coeffs, 0), base_at(coeffs, 1)])
+    }
+
+    fn to_base_coeffs(&self) -> Vec<FFieldUnit> {
+        self.0.to_vec()
+    }
+
+    fn frobenius(&self) -> Self {
+        // u^p = u * 5^((p - 1) / 2) = -u
+        Self([self.0[0], FFieldUnit::zero() - self.0[1]])
+    }
+}
+
+/// FFieldUnit[u] / (u^3 - 5), about 93 bits.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CubicExt(pub [FFieldUnit; 3]);
+
+impl CubicExt {
+    pub fn new(a0: FFieldUnit, a1: FFieldUnit, a2: FFieldUnit) -> Self {
+        Self([a0, a1, a2])
+    }
+
+    pub fn inverse(&self) -> Self {
+        let w = nonresidue();
+        let [a0, a1, a2] = self.0;
+        // adjugate of the multiplication-by-x matrix, first column
+        let t0 = a0 * a0 - w * a1 * a2;
+        let t1 = w * a2 * a2 - a0 * a1;
+        let t2 = a1 * a1 - a0 * a2;
+        let norm = a0 * t0 + w * (a2 * t1 + a1 * t2);
+        let n_inv = norm.inverse();
+        Self([t0 * n_inv, t1 * n_inv, t2 * n_inv])
+    }
+}
+
+impl Add for CubicExt {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self([
+            self.0[0] + other.0[0],
+            self.0[1] + other.0[1],
+            self.0[2] + other.0[2],
+        ])
+    }
+}
+
+impl Sub for CubicExt {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self([
+            self.0[0] - other.0[0],
+            self.0[1] - other.0[1],
+            self.0[2] - other.0[2],
+        ])
+    }
+}
+
+impl Mul for CubicExt {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let w = nonresidue();
+        let ([a0, a1, a2], [b0, b1, b2]) = (self.0, other.0);
+        Self([
+            a0 * b0 + w * (a1 * b2 + a2 * b1),
+            a0 * b1 + a1 * b0 + w * a2 * b2,
+            a0 * b2 + a1 * b1 + a2 * b0,
+        ])
+    }
+}
+
+impl Mul<FFieldUnit> for CubicExt {
+    type Output = Self;
+    fn mul(self, other: FFieldUnit) -> Self {
+        Self([self.0[0] * other, self.0[1] * other, self.0[2] * other])
+    }
+}
+
+impl Div for CubicExt {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inverse()
+    }
+}
+
+impl fmt::Display for CubicExt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.0[0], self.0[1], self.0[2])
+    }
+}
+
+impl Field for CubicExt {
+    const TWO_ADICITY: u32 = FFieldUnit::TWO_ADICITY;
+    const NUM_BYTES: usize = 3 * FFieldUnit::NUM_BYTES;
+
+    fn zero() -> Self {
+        Self::from_base(FFieldUnit::zero())
+    }
+
+    fn one() -> Self {
+        Self::from_base(FFieldUnit::one())
+    }
+
+    fn from_u128(n: u128) -> Self {
+        Self::from_base(FFieldUnit::from_u128(n))
+    }
+
+    fn inverse(&self) -> Self {
+        self.inverse()
+    }
+
+    fn two_adic_generator() -> Self {
+        Self::from_base(FFieldUnit::two_adic_generator())
+    }
+
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        self.0.iter().flat_map(|c| c.to_canonical_bytes()).collect()
+    }
+
+    fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        base_coeffs_from_bytes(bytes, Self::DEGREE).map(|c| Self::from_base_slice(&c))
+    }
+
+    fn from_random_u128(n: u128) -> Self {
+        Self::from_base_slice(&base_coeffs_from_random_u128(n, Self::DEGREE))
+    }
+
+    fn from_random_digest(digest: &[u8]) -> Self {
+        Self::from_base_slice(&base_coeffs_from_random_digest(digest, Self::DEGREE))
+    }
+}
+
+impl ExtensionField for CubicExt {
+    type Base = FFieldUnit;
+    const DEGREE: usize = 3;
+
+    fn from_base(b: FFieldUnit) -> Self {
+        Self([b, FFieldUnit::zero(), FFieldUnit::zero()])
+    }
+
+    fn from_base_slice(coeffs: &[FFieldUnit]) -> Self {
+        Self([base_at(coeffs, 0), base_at(coeffs, 1), base_at(coeffs, 2)])
+    }
+
+    fn to_base_coeffs(&self) -> Vec<FFieldUnit> {
+        self.0.to_vec()
+    }
+
+    fn frobenius(&self) -> Self {
+        // u^p = u * 5^((p - 1) / 3) = omega * u, omega a primitive cube root of unity
+        let omega = FFieldUnit::pow(nonresidue(), 1 << 30);
+        Self([self.0[0], self.0[1] * omega, self.0[2] * omega * omega])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::Channel;
+    use crate::FFPoly::FFPoly;
+
+    fn units(v: &[i128]) -> Vec<FFieldUnit> {
+        v.iter().map(|x| FFieldUnit::new(*x)).collect()
+    }
+
+    #[test]
+    fn test_quadratic_arithmetic() {
+        let u = QuadExt::from_base_slice(&units(&[0, 1]));
+        assert_eq!(u * u, QuadExt::from_base(FFieldUnit::new(5)));
+
+        let x = QuadExt::from_base_slice(&units(&[1234, 98765]));
+        assert_eq!(x * x.inverse(), QuadExt::one());
+        // frobenius is the p-th power map and x * frob(x) is the norm
+        assert_eq!(x * x.frobenius(), QuadExt::from_base(x.norm()));
+        assert_eq!(x.frobenius().frobenius(), x);
+    }
+
+    #[test]
+    fn test_cubic_arithmetic() {
+        let u = CubicExt::from_base_slice(&units(&[0, 1]));
+        assert_eq!(u * u * u, CubicExt::from_base(FFieldUnit::new(5)));
+
+        let x = CubicExt::from_base_slice(&units(&[123, 456789, 987654321]));
+        assert_eq!(x * x.inverse(), CubicExt::one());
+        let p = FFieldUnit::modulo_prime() as u64;
+        assert_eq!(x.frobenius(), Field::pow(&x, p));
+        assert_eq!(x.frobenius().frobenius().frobenius(), x);
+        // the norm x * x^p * x^(p^2) lies in the base field
+        let n = x * x.frobenius() * x.frobenius().frobenius();
+        assert_eq!(n.0[1], FFieldUnit::zero());
+        assert_eq!(n.0[2], FFieldUnit::zero());
+    }
+
+    #[test]
+    fn test_eval_ext_agrees_with_base_eval() {
+        let f: FFPoly = FFPoly::new(units(&[7, 0, 3, 1]), "x");
+        let x = FFieldUnit::new(11);
+        assert_eq!(
+            f.eval_ext(&QuadExt::from_base(x)),
+            QuadExt::from_base(f.eval(&x))
+        );
+
+        // f(u) = 7 + 3u^2 + u^3 = 12 + 3u^2 over u^3 = 5
+        let u = CubicExt::from_base_slice(&units(&[0, 1]));
+        assert_eq!(
+            f.eval_ext(&u),
+            CubicExt::from_base_slice(&units(&[12, 0, 3]))
+        );
+    }
+
+    #[test]
+    fn test_channel_samples_extension_elements() {
+        let mut chan = Channel::new();
+        chan.send("root");
+        let a: CubicExt = chan.receive_rnd_ext();
+        let b: CubicExt = chan.receive_rnd_ext();
+        assert_ne!(a, b);
+        assert_ne!(a.0[1], FFieldUnit::zero());
+    }
+
+    #[test]
+    fn test_generic_draws_fill_every_coefficient() {
+        let mut chan = Channel::new();
+        chan.send("root");
+        let q: QuadExt = chan.receive_rnd_field();
+        assert_ne!(q.0[1], FFieldUnit::zero());
+        let c: CubicExt = chan.receive_rnd_field();
+        assert!(c.0[1..].iter().all(|x| !x.is_zero()));
+
+        let digest = crate::utils::hash256_vec(b"root");
+        let c = CubicExt::from_random_digest(&digest);
+        assert!(c.0.iter().all(|x| !x.is_zero()));
+        assert_eq!(
+            c.0[2],
+            <FFieldUnit as Field>::from_random_digest(&digest[22..])
+        );
+    }
+}
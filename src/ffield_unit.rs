@@ -1,16 +1,34 @@
-use std::ops::{Add, Div, Mul, Sub};
-
-#[derive(Debug, Copy, Clone)]
-pub struct FFieldUnit(pub i128);
+use crate::error::Error;
+use crate::field::Field;
+use crate::montgomery;
+use num_traits::{Inv, Num, One, Pow, Zero};
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+use std::str::FromStr;
+
+const P: u32 = 3 * (1 << 30) + 1;
+const P_INV: u32 = montgomery::inv_mod_2_32(P);
+
+/// element of the prime field of order 3 * 2^30 + 1.
+/// internally the value is kept reduced in Montgomery form (x * 2^32 mod p), so
+/// every operation is a handful of u32/u64 instructions; `inner()` gives back the
+/// canonical representative.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FFieldUnit(u32);
 
 impl FFieldUnit {
     pub fn new(n: i128) -> Self {
-        //println!("new FF: {:?}", n);
-        Self(n.rem_euclid(Self::modulo_prime()))
+        Self::from_canonical(n.rem_euclid(Self::modulo_prime()) as u32)
+    }
+
+    const fn from_canonical(n: u32) -> Self {
+        Self(montgomery::to_monty(n, P, P_INV))
     }
 
     pub fn one() -> Self {
-        Self(1)
+        Self(montgomery::r_mod(P))
     }
 
     pub fn zero() -> Self {
@@ -18,11 +36,26 @@ impl FFieldUnit {
     }
 
     pub fn inner(&self) -> i128 {
-        self.0
+        montgomery::from_monty(self.0, P, P_INV) as i128
     }
 
     pub fn is_zero(&self) -> bool {
-        self.clone() == Self::zero()
+        *self == Self::zero()
+    }
+
+    /// 4-byte little-endian encoding of the canonical value.
+    pub fn to_bytes(&self) -> [u8; 4] {
+        (self.inner() as u32).to_le_bytes()
+    }
+
+    /// inverse of `to_bytes`, values >= p are rejected rather than reduced.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let arr: [u8; 4] = bytes.try_into().map_err(|_| Error::InvalidLength)?;
+        let n = u32::from_le_bytes(arr);
+        if n >= P {
+            return Err(Error::NonCanonical);
+        }
+        Ok(Self::from_canonical(n))
     }
 
     pub fn generator_value() -> i128 {
@@ -33,21 +66,27 @@ impl FFieldUnit {
         Self::new(Self::generator_value())
     }
 
+    /// generator of the order-1024 subgroup used for the FibonacciSq trace.
     pub fn generator() -> Self {
-        Self::pow(Self::new(Self::generator_value()), 3 * 2_u32.pow(20))
+        <Self as Field>::root_of_unity(10).unwrap()
     }
 
     pub fn modulo_prime() -> i128 {
-        3 * 2_i128.pow(30) + 1
+        P as i128
+    }
+
+    /// p - 1 = 3 * 2^30
+    pub const fn two_adicity() -> u32 {
+        30
     }
 
     pub fn pow(n: Self, e: u32) -> Self {
         let mut x = n;
         let mut p = e;
-        let mut tot = Self::new(1i128);
+        let mut tot = Self::one();
         while p != 0 {
             if p % 2 == 1 {
-                tot = tot * x;
+                tot *= x;
             }
             p /= 2;
             x = x * x;
@@ -55,19 +94,20 @@ impl FFieldUnit {
         tot
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn eq(r: Self, l: Self) -> bool {
         r.0 == l.0
     }
 
     pub fn neg(&self) -> Self {
-        Self::zero() - self.clone()
+        Self::zero() - *self
     }
 
     pub fn inverse(&self) -> Self {
         let mut t = 0;
         let mut new_t = 1;
         let mut m = Self::modulo_prime();
-        let mut v = self.0;
+        let mut v = self.inner();
         while v != 0 {
             let q = m / v;
             (t, new_t) = (new_t, (t - (q * new_t)));
@@ -78,6 +118,89 @@ impl FFieldUnit {
     }
 }
 
+// p - 1 = ODD_PART * 2^TWO_ADICITY
+const ODD_PART: u64 = 3;
+const GROUP_ORDER_PRIME_FACTORS: [u64; 2] = [2, 3];
+
+impl FFieldUnit {
+    /// 1 for non-zero squares, -1 for non-squares and 0 for zero.
+    pub fn legendre(&self) -> i8 {
+        if self.is_zero() {
+            return 0;
+        }
+        let e = Field::pow(self, (P as u64 - 1) / 2);
+        if e == Self::one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// a square root via Tonelli-Shanks, None when self is not a square.
+    /// of the two roots r and -r the one returned is unspecified.
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(*self);
+        }
+        if self.legendre() != 1 {
+            return None;
+        }
+        // 5 generates the group, so it is a non-square
+        let mut m = Self::two_adicity();
+        let mut c = Field::pow(&Self::ffgenerator(), ODD_PART);
+        let mut t = Field::pow(self, ODD_PART);
+        let mut r = Field::pow(self, ODD_PART.div_ceil(2));
+        while t != Self::one() {
+            // least i with t^(2^i) = 1
+            let mut i = 0;
+            let mut t2i = t;
+            while t2i != Self::one() {
+                t2i = t2i * t2i;
+                i += 1;
+            }
+            let b = (0..m - i - 1).fold(c, |acc, _| acc * acc);
+            m = i;
+            c = b * b;
+            t *= c;
+            r *= b;
+        }
+        Some(r)
+    }
+
+    /// smallest k > 0 with self^k = 1, None for zero.
+    pub fn multiplicative_order(&self) -> Option<u64> {
+        if self.is_zero() {
+            return None;
+        }
+        let mut order = P as u64 - 1;
+        for q in GROUP_ORDER_PRIME_FACTORS {
+            while order.is_multiple_of(q) && Field::pow(self, order / q) == Self::one() {
+                order /= q;
+            }
+        }
+        Some(order)
+    }
+
+    /// whether self generates the whole multiplicative group.
+    pub fn is_primitive_root(&self) -> bool {
+        self.multiplicative_order() == Some(P as u64 - 1)
+    }
+
+    /// self^n = 1
+    pub fn is_nth_root_of_unity(&self, n: u64) -> bool {
+        !self.is_zero() && Field::pow(self, n) == Self::one()
+    }
+
+    /// self^n = 1 and no smaller positive power is 1.
+    pub fn is_primitive_nth_root_of_unity(&self, n: u64) -> bool {
+        self.multiplicative_order() == Some(n)
+    }
+}
+
 impl From<i128> for FFieldUnit {
     fn from(n: i128) -> Self {
         Self::new(n)
@@ -87,70 +210,66 @@ impl From<i128> for FFieldUnit {
 impl Add<i128> for FFieldUnit {
     type Output = Self;
     fn add(self, other: i128) -> Self {
-        let m = Self::modulo_prime();
-        Self::new((self.0.rem_euclid(m)) + (other.rem_euclid(m)))
+        self + Self::new(other)
     }
 }
 
 impl Add for FFieldUnit {
     type Output = Self;
+    #[inline]
     fn add(self, other: Self) -> Self {
-        let m = Self::modulo_prime();
-        Self::new((self.0.rem_euclid(m)) + (other.0.rem_euclid(m)))
+        Self(montgomery::add(self.0, other.0, P))
     }
 }
 
 impl Add<&FFieldUnit> for FFieldUnit {
     type Output = Self;
     fn add(self, other: &Self) -> Self {
-        let m = Self::modulo_prime();
-        Self::new((self.0.rem_euclid(m)) + (other.0.rem_euclid(m)))
+        self + *other
     }
 }
 
 impl Sub for FFieldUnit {
     type Output = Self;
+    #[inline]
     fn sub(self, other: Self) -> Self {
-        let m = Self::modulo_prime();
-        Self::new((self.0.rem_euclid(m)) - (other.0.rem_euclid(m)))
+        Self(montgomery::sub(self.0, other.0, P))
     }
 }
 
 impl Sub<i128> for FFieldUnit {
     type Output = Self;
     fn sub(self, other: i128) -> Self {
-        let m = Self::modulo_prime();
-        Self::new((self.0.rem_euclid(m)) - (other.rem_euclid(m)))
+        self - Self::new(other)
     }
 }
 
 impl Mul<i128> for FFieldUnit {
     type Output = Self;
     fn mul(self, other: i128) -> Self {
-        let m = Self::modulo_prime();
-        Self::new((self.0.rem_euclid(m)) * (other.rem_euclid(m)))
+        self * Self::new(other)
     }
 }
 
 impl Mul for FFieldUnit {
     type Output = Self;
+    #[inline]
     fn mul(self, other: Self) -> Self {
-        let m = Self::modulo_prime();
-        Self::new((self.0.rem_euclid(m)) * (other.0.rem_euclid(m)))
+        Self(montgomery::mul(self.0, other.0, P, P_INV))
     }
 }
 
 impl Mul<&FFieldUnit> for FFieldUnit {
     type Output = Self;
     fn mul(self, other: &Self) -> Self {
-        let m = Self::modulo_prime();
-        Self::new((self.0.rem_euclid(m)) * (other.0.rem_euclid(m)))
+        self * *other
     }
 }
 
 impl Div for FFieldUnit {
     type Output = Self;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, other: Self) -> Self {
         self * other.inverse()
     }
@@ -159,26 +278,252 @@ impl Div for FFieldUnit {
 impl Div<i128> for FFieldUnit {
     type Output = Self;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, other: i128) -> Self {
         self * Self::new(other).inverse()
     }
 }
 
+impl Sub<&FFieldUnit> for FFieldUnit {
+    type Output = Self;
+    fn sub(self, other: &Self) -> Self {
+        self - *other
+    }
+}
+
+impl Div<&FFieldUnit> for FFieldUnit {
+    type Output = Self;
+    fn div(self, other: &Self) -> Self {
+        self / *other
+    }
+}
+
+/// every non-zero element divides every other, so the remainder is always zero.
+impl Rem for FFieldUnit {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        assert!(!other.is_zero(), "division by 0!");
+        Self::zero()
+    }
+}
+
+impl Neg for FFieldUnit {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::zero() - self
+    }
+}
+
+impl AddAssign for FFieldUnit {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for FFieldUnit {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for FFieldUnit {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign for FFieldUnit {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl Sum for FFieldUnit {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a FFieldUnit> for FFieldUnit {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl Product for FFieldUnit {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a FFieldUnit> for FFieldUnit {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+impl From<u64> for FFieldUnit {
+    fn from(n: u64) -> Self {
+        Self::from_canonical((n % P as u64) as u32)
+    }
+}
+
+impl From<u32> for FFieldUnit {
+    fn from(n: u32) -> Self {
+        Self::from(n as u64)
+    }
+}
+
+impl TryFrom<&[u8]> for FFieldUnit {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl Zero for FFieldUnit {
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+impl One for FFieldUnit {
+    fn one() -> Self {
+        Self::one()
+    }
+}
+
+impl Num for FFieldUnit {
+    type FromStrRadixErr = Error;
+
+    /// canonical values only, like `from_str`.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Error> {
+        if s.is_empty() || !s.chars().all(|c| c.is_digit(radix)) {
+            return Err(Error::InvalidDigit);
+        }
+        let n = u64::from_str_radix(s, radix).map_err(|_| Error::NonCanonical)?;
+        if n >= P as u64 {
+            return Err(Error::NonCanonical);
+        }
+        Ok(Self::from_canonical(n as u32))
+    }
+}
+
+impl Pow<u32> for FFieldUnit {
+    type Output = Self;
+    fn pow(self, e: u32) -> Self {
+        Self::pow(self, e)
+    }
+}
+
+impl Pow<u64> for FFieldUnit {
+    type Output = Self;
+    fn pow(self, e: u64) -> Self {
+        Field::pow(&self, e)
+    }
+}
+
+impl Pow<u128> for FFieldUnit {
+    type Output = Self;
+    fn pow(self, e: u128) -> Self {
+        // x^(p - 1) = 1 for x != 0, so the exponent only matters mod p - 1
+        let order = P as u128 - 1;
+        match (self.is_zero(), e) {
+            (_, 0) => Self::one(),
+            (true, _) => Self::zero(),
+            (false, _) => Field::pow(&self, (e % order) as u64),
+        }
+    }
+}
+
+impl Inv for FFieldUnit {
+    type Output = Self;
+    fn inv(self) -> Self {
+        self.inverse()
+    }
+}
+
 impl PartialEq<u32> for FFieldUnit {
     fn eq(&self, other: &u32) -> bool {
-        self.0 == other.to_owned().into()
+        self.inner() == other.to_owned().into()
+    }
+}
+
+impl fmt::Debug for FFieldUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FFieldUnit({})", self.inner())
+    }
+}
+
+impl fmt::Display for FFieldUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.inner())
     }
 }
 
-impl PartialEq<FFieldUnit> for FFieldUnit {
-    fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
+impl FromStr for FFieldUnit {
+    type Err = Error;
+
+    /// decimal, or hex when prefixed with 0x. values >= p are rejected.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => Self::from_str_radix(hex, 16),
+            None => Self::from_str_radix(s, 10),
+        }
     }
 }
 
-impl ToString for FFieldUnit {
-    fn to_string(&self) -> String {
-        format!("{}", self.0)
+impl Field for FFieldUnit {
+    const TWO_ADICITY: u32 = Self::two_adicity();
+    const NUM_BYTES: usize = 4;
+
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn one() -> Self {
+        Self::one()
+    }
+
+    fn from_u128(n: u128) -> Self {
+        Self::from_canonical((n % P as u128) as u32)
+    }
+
+    fn inverse(&self) -> Self {
+        self.inverse()
+    }
+
+    fn two_adic_generator() -> Self {
+        // 5 generates the whole group of order 3 * 2^30
+        Self::pow(Self::ffgenerator(), 3)
+    }
+
+    /// 5^((p - 1) / n), which agrees with `root_of_unity` on powers of two.
+    fn root_of_unity_of_order(n: u64) -> Option<Self> {
+        let order = P as u64 - 1;
+        if n == 0 || !order.is_multiple_of(n) {
+            return None;
+        }
+        Some(Field::pow(&Self::ffgenerator(), order / n))
+    }
+
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+
+    fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_bytes(bytes)
+    }
+
+    fn from_random_u128(n: u128) -> Self {
+        // same range the channel has always drawn from: [0, p]
+        Self::new((n % (P as u128 + 1)) as i128)
     }
 }
 
@@ -189,7 +534,7 @@ mod tests {
     #[test]
     fn creates_an_instance() {
         let ffu = FFieldUnit::new(1);
-        assert_eq!(ffu.0, 1);
+        assert_eq!(ffu.inner(), 1);
     }
 
     #[test]
@@ -197,14 +542,14 @@ mod tests {
         let g_v = FFieldUnit::generator_value();
         assert_eq!(g_v, 5_i128);
         let g = FFieldUnit::generator();
-        assert_eq!(g.0, 1855261384_i128);
+        assert_eq!(g.inner(), 1855261384_i128);
     }
 
     #[test]
     fn pow_works() {
         let pow = FFieldUnit::pow(FFieldUnit::new(13), 17);
         assert_eq!(
-            pow.0,
+            pow.inner(),
             13_i128.pow(17).rem_euclid(FFieldUnit::modulo_prime())
         );
     }
@@ -276,7 +621,6 @@ mod tests {
     fn div_of_similar_types_works() {
         let f1 = FFieldUnit::new(4);
         let f2 = FFieldUnit::new(2);
-        let m = FFieldUnit::modulo_prime();
 
         assert_eq!(f1 / f2, FFieldUnit::new(2));
     }
@@ -288,4 +632,155 @@ mod tests {
 
         assert_eq!(f1 / f2, FFieldUnit::new(2));
     }
+
+    #[test]
+    fn inner_is_canonical_representative() {
+        let m = FFieldUnit::modulo_prime();
+        assert_eq!(FFieldUnit::new(-1).inner(), m - 1);
+        assert_eq!(FFieldUnit::new(m + 7).inner(), 7);
+        let (a, b) = (m - 2, m - 3);
+        assert_eq!(
+            (FFieldUnit::new(a) * FFieldUnit::new(b)).inner(),
+            (a * b).rem_euclid(m)
+        );
+        assert_eq!((FFieldUnit::new(a) + FFieldUnit::new(b)).inner(), m - 5);
+        assert_eq!(format!("{:?}", FFieldUnit::new(3)), "FFieldUnit(3)");
+    }
+
+    #[test]
+    fn sqrt_works() {
+        for n in [0, 1, 4, 1234567, 3221225472] {
+            let x = FFieldUnit::new(n);
+            let sq = x * x;
+            let r = sq.sqrt().unwrap();
+            assert_eq!(r * r, sq);
+            assert!(r == x || r == x.neg());
+        }
+        // generators are never squares
+        assert_eq!(FFieldUnit::ffgenerator().sqrt(), None);
+    }
+
+    #[test]
+    fn legendre_works() {
+        assert_eq!(FFieldUnit::zero().legendre(), 0);
+        assert_eq!(FFieldUnit::new(4).legendre(), 1);
+        assert_eq!(FFieldUnit::ffgenerator().legendre(), -1);
+        let n = FFieldUnit::ffgenerator();
+        assert_eq!((n * n * n).legendre(), -1);
+        assert!(!FFieldUnit::new(5).is_square());
+    }
+
+    #[test]
+    fn multiplicative_order_works() {
+        let m = FFieldUnit::modulo_prime() as u64;
+        assert_eq!(FFieldUnit::one().multiplicative_order(), Some(1));
+        assert_eq!(FFieldUnit::new(-1).multiplicative_order(), Some(2));
+        assert_eq!(FFieldUnit::zero().multiplicative_order(), None);
+        assert_eq!(FFieldUnit::generator().multiplicative_order(), Some(1024));
+        assert_eq!(
+            FFieldUnit::ffgenerator().multiplicative_order(),
+            Some(m - 1)
+        );
+    }
+
+    #[test]
+    fn primitive_root_checks_work() {
+        assert!(FFieldUnit::new(FFieldUnit::generator_value()).is_primitive_root());
+        assert!(!FFieldUnit::new(4).is_primitive_root());
+        let g = FFieldUnit::generator();
+        assert!(g.is_nth_root_of_unity(1024));
+        assert!(g.is_nth_root_of_unity(2048));
+        assert!(!g.is_nth_root_of_unity(512));
+        assert!(g.is_primitive_nth_root_of_unity(1024));
+        assert!(!g.is_primitive_nth_root_of_unity(2048));
+        assert!(!FFieldUnit::zero().is_nth_root_of_unity(1));
+    }
+
+    #[test]
+    fn byte_encoding_works() {
+        let x = FFieldUnit::new(-1);
+        assert_eq!(x.to_bytes(), [0, 0, 0, 0xc0]);
+        assert_eq!(FFieldUnit::from_bytes(&x.to_bytes()), Ok(x));
+        assert_eq!(
+            FFieldUnit::from_bytes(&[1, 0, 0, 0xc0]),
+            Err(Error::NonCanonical)
+        );
+        assert_eq!(FFieldUnit::from_bytes(&[0xff; 4]), Err(Error::NonCanonical));
+        assert_eq!(
+            FFieldUnit::from_bytes(&[1, 2, 3]),
+            Err(Error::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn parsing_works() {
+        assert_eq!(
+            "3141592".parse::<FFieldUnit>(),
+            Ok(FFieldUnit::new(3141592))
+        );
+        assert_eq!("0xc0000000".parse::<FFieldUnit>(), Ok(FFieldUnit::new(-1)));
+        assert_eq!("3221225473".parse::<FFieldUnit>(), Err(Error::NonCanonical));
+        assert_eq!(
+            "99999999999999999999999".parse::<FFieldUnit>(),
+            Err(Error::NonCanonical)
+        );
+        assert_eq!("-1".parse::<FFieldUnit>(), Err(Error::InvalidDigit));
+        assert_eq!("0x".parse::<FFieldUnit>(), Err(Error::InvalidDigit));
+        let x = FFieldUnit::new(2338775057);
+        assert_eq!(x.to_string().parse::<FFieldUnit>(), Ok(x));
+    }
+
+    #[test]
+    fn num_traits_work() {
+        fn sum_of_squares<T: Num + Copy>(xs: &[T]) -> T {
+            xs.iter().fold(T::zero(), |acc, x| acc + *x * *x)
+        }
+        let xs = [FFieldUnit::new(3), FFieldUnit::new(4)];
+        assert_eq!(sum_of_squares(&xs), FFieldUnit::new(25));
+        assert!(<FFieldUnit as Zero>::is_zero(&Zero::zero()));
+        assert_eq!(<FFieldUnit as One>::one(), FFieldUnit::one());
+        assert_eq!(
+            <FFieldUnit as Num>::from_str_radix("ff", 16),
+            Ok(FFieldUnit::new(255))
+        );
+        assert_eq!(Inv::inv(FFieldUnit::new(2)) * 2, FFieldUnit::one());
+        assert_eq!(FFieldUnit::new(7) % FFieldUnit::new(3), FFieldUnit::zero());
+    }
+
+    #[test]
+    fn wide_exponents_work() {
+        let x = FFieldUnit::new(13);
+        let m = FFieldUnit::modulo_prime() as u128;
+        assert_eq!(Pow::pow(x, 17u64), FFieldUnit::pow(x, 17));
+        assert_eq!(
+            Pow::pow(x, 5_000_000_000u64),
+            Pow::pow(x, 5_000_000_000u128)
+        );
+        assert_eq!(Pow::pow(x, m - 1), FFieldUnit::one());
+        assert_eq!(Pow::pow(x, u128::MAX), Pow::pow(x, u128::MAX % (m - 1)));
+        assert_eq!(Pow::pow(FFieldUnit::zero(), m - 1), FFieldUnit::zero());
+        assert_eq!(Pow::pow(FFieldUnit::zero(), 0u128), FFieldUnit::one());
+    }
+
+    #[test]
+    fn operator_set_works() {
+        let mut x = FFieldUnit::new(10);
+        x += FFieldUnit::new(5);
+        x -= FFieldUnit::new(3);
+        x *= FFieldUnit::new(2);
+        x /= FFieldUnit::new(4);
+        assert_eq!(x, FFieldUnit::new(6));
+        assert_eq!(-x + x, FFieldUnit::zero());
+
+        let xs: Vec<FFieldUnit> = (1..6).map(FFieldUnit::new).collect();
+        assert_eq!(xs.iter().sum::<FFieldUnit>(), FFieldUnit::new(15));
+        assert_eq!(
+            xs.iter().copied().product::<FFieldUnit>(),
+            FFieldUnit::new(120)
+        );
+
+        assert_eq!(FFieldUnit::from(3221225474u64), FFieldUnit::one());
+        let bytes: &[u8] = &[7, 0, 0, 0];
+        assert_eq!(FFieldUnit::try_from(bytes), Ok(FFieldUnit::new(7)));
+    }
 }
@@ -1,11 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use std::ops::Mul;
     use std::time::Instant;
 
     use crate::{
         channel::{serialize, Channel},
+        domain::Domain,
         ffield_unit::FFieldUnit,
+        field::Field,
         merkle::MerkleTree,
         utils::hash256_str,
         FFPoly::{interpolate_poly, FFPoly},
@@ -45,10 +46,8 @@ mod tests {
 
         let generator = FFieldUnit::generator();
         // group: G
-        let group: Vec<FFieldUnit> = (0..1024)
-            .into_iter()
-            .map(|n| FFieldUnit::pow(generator, n))
-            .collect();
+        let group: Vec<FFieldUnit> = Domain::subgroup(10).unwrap().elements();
+        assert_eq!(group[1], generator);
 
         let group_len = group.len();
 
@@ -76,19 +75,15 @@ mod tests {
         // = 2^3 * 2^10 = 2^13 = 8192
         //
         let w = FFieldUnit::ffgenerator();
-        let h_generator = FFieldUnit::pow(w, 2u32.pow(30).mul(3) / 8192);
+        let h_generator = FFieldUnit::root_of_unity(13).unwrap();
         assert_eq!(h_generator, FFieldUnit::new(1734477367));
 
         // generate the group same as we did before
         // here len will be 8 x that
-        let h_group_coset: Vec<FFieldUnit> = (0..8192)
-            .into_iter()
-            .map(|i| FFieldUnit::pow(h_generator, i))
-            .collect();
+        let h_group_coset: Vec<FFieldUnit> = Domain::subgroup(13).unwrap().elements();
 
         assert_eq!(h_group_coset.len(), 8 * group_len);
-        let eval_domain: Vec<FFieldUnit> =
-            h_group_coset.clone().into_iter().map(|h| w * h).collect();
+        let eval_domain: Vec<FFieldUnit> = Domain::coset(13, w).unwrap().elements();
         assert_eq!(eval_domain.len(), 8 * group_len);
 
         let w_inv = w.inverse();
@@ -107,7 +102,7 @@ mod tests {
         });
 
         // now time to evaluate on the Coset
-        let f_evals: Vec<FFieldUnit> = eval_domain.iter().map(|ed| f.eval(ed)).collect();
+        let f_evals: Vec<FFieldUnit> = f.lde(8, w);
         let serialized = serialize(&f_evals[..]);
         assert_eq!(
             "1d357f674c27194715d1440f6a166e30855550cb8cb8efeb72827f6a1bf9b5bb",
@@ -216,7 +211,7 @@ mod tests {
         let (denom_2, r_denom_2) = numer_of_denom_2.qdiv(&denom_of_denom_2);
         // assert_eq!(r_denom_2, FFPoly::zero(var));
 
-        let (q_2, r_2) = numer_2.qdiv(&denom_2);
+        let (q_2, r_2) = numer_2.qdiv_fast(&denom_2);
         // assert_eq!(r_2, FFPoly::zero(var));
 
         // from onwards we use channel
@@ -225,11 +220,11 @@ mod tests {
         let cp_2 = q_2.scalar_mul(&chan.receive_rnd_ffunit());
 
         let cp = cp_0 + cp_1 + cp_2;
-        let cp_ev: Vec<FFieldUnit> = eval_dom.iter().map(|d| cp.eval(&d)).collect();
+        let cp_ev: Vec<FFieldUnit> = cp.eval_batch(&eval_dom);
         let mut mkt = MerkleTree::new(&cp_ev);
         mkt.build_tree();
         assert_eq!(
-            "e7eef880221bc0089d3b8f7997b334adcc1a9ad9893cad2df6acdc9b3acdb79c",
+            "d9db628d00541b4bf8b89b81935bac48f2af465a27484ab06bd324a1653ec84e",
             mkt.root()
         );
         chan.send(&mkt.root());
@@ -0,0 +1,231 @@
+use crate::error::Error;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Sub};
+
+/// a prime (or extension) field the polynomial, commitment and transcript code can
+/// work over. FFieldUnit is the reference implementation; anything implementing this
+/// trait can be plugged into FFPoly, MerkleTree and Channel.
+pub trait Field:
+    Copy
+    + Clone
+    + Debug
+    + Display
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    /// the multiplicative group has a subgroup of order 2^TWO_ADICITY, generated by
+    /// `two_adic_generator`. for prime fields this is the full 2-part of p - 1,
+    /// extensions expose the subgroup of their base field.
+    const TWO_ADICITY: u32;
+
+    /// number of bytes produced by `to_canonical_bytes`.
+    const NUM_BYTES: usize;
+
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    /// reduces n into the field.
+    fn from_u128(n: u128) -> Self;
+
+    /// multiplicative inverse, panics on zero.
+    fn inverse(&self) -> Self;
+
+    /// element of order exactly 2^TWO_ADICITY.
+    fn two_adic_generator() -> Self;
+
+    /// fixed-width little-endian encoding of the canonical representative.
+    fn to_canonical_bytes(&self) -> Vec<u8>;
+
+    /// inverse of `to_canonical_bytes`, rejects inputs of the wrong size and
+    /// values that are not fully reduced.
+    fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, Error>;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    fn neg(&self) -> Self {
+        Self::zero() - *self
+    }
+
+    fn square(&self) -> Self {
+        *self * *self
+    }
+
+    fn pow(&self, e: u64) -> Self {
+        let mut x = *self;
+        let mut p = e;
+        let mut tot = Self::one();
+        while p != 0 {
+            if p % 2 == 1 {
+                tot = tot * x;
+            }
+            p /= 2;
+            x = x * x;
+        }
+        tot
+    }
+
+    /// primitive 2^log_n-th root of unity, None when log_n exceeds TWO_ADICITY.
+    fn root_of_unity(log_n: u32) -> Option<Self> {
+        if log_n > Self::TWO_ADICITY {
+            return None;
+        }
+        let root =
+            (log_n..Self::TWO_ADICITY).fold(Self::two_adic_generator(), |acc, _| acc.square());
+        Some(root)
+    }
+
+    /// primitive n-th root of unity, None when the field has no subgroup of order n.
+    /// the default only knows the power-of-two subgroups; fields whose group order has
+    /// other small factors override it.
+    fn root_of_unity_of_order(n: u64) -> Option<Self> {
+        if !n.is_power_of_two() {
+            return None;
+        }
+        Self::root_of_unity(n.trailing_zeros())
+    }
+
+    /// maps the pseudo-random integer drawn by a Channel onto a field element.
+    /// the default is plain reduction.
+    fn from_random_u128(n: u128) -> Self {
+        Self::from_u128(n)
+    }
+
+    /// maps a pseudo-random big-endian digest onto a field element, used by a Channel
+    /// for fields too wide to be covered by `from_random_u128`. the default reduces
+    /// the whole digest modulo the characteristic, so it only suits prime fields.
+    fn from_random_digest(digest: &[u8]) -> Self {
+        let base = Self::from_u128(256);
+        digest.iter().fold(Self::zero(), |acc, b| {
+            acc * base + Self::from_u128(*b as u128)
+        })
+    }
+}
+
+/// inverts every element of vals with a single field inversion and 3(n - 1)
+/// multiplications (Montgomery's trick). zero entries have no inverse and are
+/// skipped: they come back as zero and do not affect the other results.
+pub fn batch_inverse<F: Field>(vals: &[F]) -> Vec<F> {
+    let mut res = vals.to_vec();
+    batch_inverse_in_place(&mut res);
+    res
+}
+
+/// same as `batch_inverse`, overwriting vals with the inverses.
+pub fn batch_inverse_in_place<F: Field>(vals: &mut [F]) {
+    // prefix[i] = product of the non-zero vals[..i]
+    let mut prefix = Vec::with_capacity(vals.len());
+    let mut acc = F::one();
+    for v in vals.iter() {
+        prefix.push(acc);
+        if !v.is_zero() {
+            acc = acc * *v;
+        }
+    }
+    if acc.is_zero() {
+        return;
+    }
+
+    // inv holds the inverse of the product of the non-zero vals[..=i]
+    let mut inv = acc.inverse();
+    for (v, pre) in vals.iter_mut().zip(prefix).rev() {
+        if v.is_zero() {
+            continue;
+        }
+        let next_inv = inv * *v;
+        *v = inv * pre;
+        inv = next_inv;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffield_unit::FFieldUnit;
+
+    fn pow_of_two<F: Field>(x: F, k: u32) -> F {
+        (0..k).fold(x, |acc, _| acc.square())
+    }
+
+    #[test]
+    fn test_two_adic_generator_has_full_order() {
+        let g = FFieldUnit::two_adic_generator();
+        let k = FFieldUnit::TWO_ADICITY;
+        assert_eq!(pow_of_two(g, k), FFieldUnit::one());
+        assert_ne!(pow_of_two(g, k - 1), FFieldUnit::one());
+    }
+
+    #[test]
+    fn test_generic_pow_and_inverse() {
+        let x = FFieldUnit::new(1234567);
+        assert_eq!(Field::pow(&x, 17), FFieldUnit::pow(x, 17));
+        assert_eq!(x * Field::inverse(&x), FFieldUnit::one());
+    }
+
+    #[test]
+    fn test_random_digest_is_reduced() {
+        assert_eq!(
+            <FFieldUnit as Field>::from_random_digest(&[1, 0, 2]),
+            FFieldUnit::new(65538)
+        );
+        // 2^32 = (3 * 2^30 + 1) + 2^30 - 1
+        assert_eq!(
+            <FFieldUnit as Field>::from_random_digest(&[1, 0, 0, 0, 0]),
+            FFieldUnit::new((1 << 30) - 1)
+        );
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        for log_n in [0, 1, 10, 13, 30] {
+            let w = FFieldUnit::root_of_unity(log_n).unwrap();
+            assert_eq!(pow_of_two(w, log_n), FFieldUnit::one());
+            if log_n > 0 {
+                assert_ne!(pow_of_two(w, log_n - 1), FFieldUnit::one());
+            }
+        }
+        assert_eq!(FFieldUnit::root_of_unity(31), None);
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        let vals: Vec<FFieldUnit> = (1..50).map(FFieldUnit::new).collect();
+        let invs = batch_inverse(&vals);
+        for (v, i) in vals.iter().zip(invs.iter()) {
+            assert_eq!(*i, v.inverse());
+        }
+        assert!(batch_inverse::<FFieldUnit>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_batch_inverse_skips_zeroes() {
+        let mut vals = vec![
+            FFieldUnit::zero(),
+            FFieldUnit::new(3),
+            FFieldUnit::zero(),
+            FFieldUnit::new(-7),
+            FFieldUnit::zero(),
+        ];
+        batch_inverse_in_place(&mut vals);
+        assert_eq!(vals[0], FFieldUnit::zero());
+        assert_eq!(vals[1], FFieldUnit::new(3).inverse());
+        assert_eq!(vals[2], FFieldUnit::zero());
+        assert_eq!(vals[3], FFieldUnit::new(-7).inverse());
+        assert_eq!(vals[4], FFieldUnit::zero());
+
+        let mut zeroes = vec![FFieldUnit::zero(); 3];
+        batch_inverse_in_place(&mut zeroes);
+        assert_eq!(zeroes, vec![FFieldUnit::zero(); 3]);
+    }
+
+    #[test]
+    fn test_canonical_bytes() {
+        let x = FFieldUnit::new(0x01020304);
+        assert_eq!(x.to_canonical_bytes(), vec![4, 3, 2, 1]);
+    }
+}
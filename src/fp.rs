@@ -0,0 +1,256 @@
+use crate::error::Error;
+use crate::field::Field;
+use crate::montgomery;
+use crate::FFPoly::FFPoly;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// element of the prime field of order P, where P is an odd prime below 2^32 and G
+/// generates its multiplicative group. two-adicity and the two-adic generator are
+/// derived from the parameters, so any NTT-friendly 32-bit prime is one type alias
+/// away. like FFieldUnit, values are kept reduced in Montgomery form.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Fp<const P: u32, const G: u32>(u32);
+
+/// 15 * 2^27 + 1
+pub type BabyBear = Fp<2013265921, 31>;
+/// 2^31 - 2^24 + 1
+pub type KoalaBear = Fp<2130706433, 3>;
+/// 3 * 2^30 + 1, the prime behind FFieldUnit
+pub type Stark101Fp = Fp<3221225473, 5>;
+
+impl<const P: u32, const G: u32> Fp<P, G> {
+    const P_INV: u32 = montgomery::inv_mod_2_32(P);
+
+    pub fn new(n: u64) -> Self {
+        Self(montgomery::to_monty((n % P as u64) as u32, P, Self::P_INV))
+    }
+
+    pub fn one() -> Self {
+        Self(montgomery::r_mod(P))
+    }
+
+    pub fn zero() -> Self {
+        Self(0)
+    }
+
+    pub fn inner(&self) -> u32 {
+        montgomery::from_monty(self.0, P, Self::P_INV)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn generator_value() -> u32 {
+        G
+    }
+
+    pub fn ffgenerator() -> Self {
+        Self::new(G as u64)
+    }
+
+    pub fn modulo_prime() -> u32 {
+        P
+    }
+
+    pub fn two_adicity() -> u32 {
+        (P - 1).trailing_zeros()
+    }
+
+    pub fn pow(n: Self, e: u64) -> Self {
+        Field::pow(&n, e)
+    }
+
+    pub fn inverse(&self) -> Self {
+        assert!(!self.is_zero(), "inverse of 0");
+        // Fermat: x^(p - 2)
+        Self::pow(*self, P as u64 - 2)
+    }
+}
+
+impl<const P: u32, const G: u32> Add for Fp<P, G> {
+    type Output = Self;
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self(montgomery::add(self.0, other.0, P))
+    }
+}
+
+impl<const P: u32, const G: u32> Sub for Fp<P, G> {
+    type Output = Self;
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Self(montgomery::sub(self.0, other.0, P))
+    }
+}
+
+impl<const P: u32, const G: u32> Mul for Fp<P, G> {
+    type Output = Self;
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        Self(montgomery::mul(self.0, other.0, P, Self::P_INV))
+    }
+}
+
+impl<const P: u32, const G: u32> Div for Fp<P, G> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inverse()
+    }
+}
+
+impl<const P: u32, const G: u32> From<u64> for Fp<P, G> {
+    fn from(n: u64) -> Self {
+        Self::new(n)
+    }
+}
+
+impl<const P: u32, const G: u32> fmt::Debug for Fp<P, G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fp<{}>({})", P, self.inner())
+    }
+}
+
+impl<const P: u32, const G: u32> fmt::Display for Fp<P, G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.inner())
+    }
+}
+
+impl<const P: u32, const G: u32> Field for Fp<P, G> {
+    const TWO_ADICITY: u32 = (P - 1).trailing_zeros();
+    const NUM_BYTES: usize = 4;
+
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn one() -> Self {
+        Self::one()
+    }
+
+    fn from_u128(n: u128) -> Self {
+        Self::new((n % P as u128) as u64)
+    }
+
+    fn inverse(&self) -> Self {
+        self.inverse()
+    }
+
+    fn two_adic_generator() -> Self {
+        Self::pow(Self::ffgenerator(), ((P - 1) >> Self::TWO_ADICITY) as u64)
+    }
+
+    /// G^((P - 1) / n), which agrees with `root_of_unity` on powers of two.
+    fn root_of_unity_of_order(n: u64) -> Option<Self> {
+        let order = P as u64 - 1;
+        if n == 0 || !order.is_multiple_of(n) {
+            return None;
+        }
+        Some(Self::pow(Self::ffgenerator(), order / n))
+    }
+
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        self.inner().to_le_bytes().to_vec()
+    }
+
+    fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let arr: [u8; 4] = bytes.try_into().map_err(|_| Error::InvalidLength)?;
+        let n = u32::from_le_bytes(arr);
+        if n >= P {
+            return Err(Error::NonCanonical);
+        }
+        Ok(Self::new(n as u64))
+    }
+}
+
+impl<const P: u32, const G: u32> Mul<FFPoly<Fp<P, G>>> for Fp<P, G> {
+    type Output = FFPoly<Fp<P, G>>;
+
+    fn mul(self, other: FFPoly<Fp<P, G>>) -> FFPoly<Fp<P, G>> {
+        other.scalar_mul(&self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Domain;
+    use crate::ffield_unit::FFieldUnit;
+    use crate::ntt::{intt, ntt};
+
+    fn check_two_adic_generator<F: Field>() {
+        let g = F::two_adic_generator();
+        let half = (0..F::TWO_ADICITY - 1).fold(g, |acc, _| acc.square());
+        assert_ne!(half, F::one());
+        assert_eq!(half.square(), F::one());
+    }
+
+    #[test]
+    fn test_two_adicity_from_parameters() {
+        assert_eq!(<BabyBear as Field>::TWO_ADICITY, 27);
+        assert_eq!(<KoalaBear as Field>::TWO_ADICITY, 24);
+        assert_eq!(<Stark101Fp as Field>::TWO_ADICITY, 30);
+        assert_eq!(BabyBear::two_adicity(), 27);
+        check_two_adic_generator::<BabyBear>();
+        check_two_adic_generator::<KoalaBear>();
+        check_two_adic_generator::<Stark101Fp>();
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let p = BabyBear::modulo_prime() as u64;
+        let (a, b) = (p - 5, 123456789);
+        let (x, y) = (BabyBear::new(a), BabyBear::new(b));
+        assert_eq!((x * y).inner() as u64, a * b % p);
+        assert_eq!((x + y).inner() as u64, (a + b) % p);
+        assert_eq!((y - x).inner() as u64, (b + p - a) % p);
+        assert_eq!(x / x, BabyBear::one());
+        assert_eq!(BabyBear::new(p), BabyBear::zero());
+    }
+
+    #[test]
+    fn test_stark101_matches_ffield_unit() {
+        let g = Stark101Fp::two_adic_generator();
+        assert_eq!(g.inner() as i128, FFieldUnit::two_adic_generator().inner());
+        for n in [0u64, 1, 2, 3141592, 3221225472] {
+            let x = Stark101Fp::new(n);
+            let y = FFieldUnit::new(n as i128);
+            assert_eq!((x * x + x).inner() as i128, (y * y + y).inner());
+        }
+    }
+
+    #[test]
+    fn test_root_of_unity_of_order() {
+        for n in [1u64, 3, 96, 3 << 30] {
+            let w = Stark101Fp::root_of_unity_of_order(n).unwrap();
+            let expected = FFieldUnit::root_of_unity_of_order(n).unwrap();
+            assert_eq!(w.inner() as i128, expected.inner());
+        }
+        assert_eq!(
+            Stark101Fp::root_of_unity_of_order(1 << 10),
+            Stark101Fp::root_of_unity(10)
+        );
+        // 15 | P - 1 for BabyBear, 127 | P - 1 for KoalaBear
+        let w = BabyBear::root_of_unity_of_order(15).unwrap();
+        assert_ne!(Field::pow(&w, 5), BabyBear::one());
+        assert_eq!(Field::pow(&w, 15), BabyBear::one());
+        assert!(KoalaBear::root_of_unity_of_order(127).is_some());
+        assert_eq!(Stark101Fp::root_of_unity_of_order(0), None);
+        assert_eq!(Stark101Fp::root_of_unity_of_order(5), None);
+
+        // 96 = 3 * 2^5
+        let coeffs: Vec<Stark101Fp> = (0..96).map(|i| Stark101Fp::new(i * i + 7)).collect();
+        let f = FFPoly::new(coeffs.clone(), "x");
+        let dom = Domain::<Stark101Fp>::subgroup_of_size(96).unwrap();
+        let mut vals = coeffs.clone();
+        ntt(&mut vals);
+        let expected: Vec<Stark101Fp> = dom.elements().iter().map(|x| f.eval(x)).collect();
+        assert_eq!(vals, expected);
+        intt(&mut vals);
+        assert_eq!(vals, coeffs);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::error::Error;
+use crate::field::Field;
+use crate::FFPoly::FFPoly;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+const P: u64 = 0xffff_ffff_0000_0001;
+// 2^64 - p = 2^32 - 1, i.e. 2^64 mod p
+const EPSILON: u64 = 0xffff_ffff;
+
+/// element of the Goldilocks field, p = 2^64 - 2^32 + 1.
+/// values are always kept canonical, in [0, p).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Goldilocks(u64);
+
+impl Goldilocks {
+    pub fn new(n: u64) -> Self {
+        Self(Self::canonicalize(n))
+    }
+
+    pub fn one() -> Self {
+        Self(1)
+    }
+
+    pub fn zero() -> Self {
+        Self(0)
+    }
+
+    pub fn inner(&self) -> u64 {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn generator_value() -> u64 {
+        7
+    }
+
+    pub fn modulo_prime() -> u64 {
+        P
+    }
+
+    pub fn pow(n: Self, e: u64) -> Self {
+        Field::pow(&n, e)
+    }
+
+    pub fn inverse(&self) -> Self {
+        assert!(!self.is_zero(), "inverse of 0");
+        // Fermat: x^(p - 2)
+        Self::pow(*self, P - 2)
+    }
+
+    #[inline(always)]
+    fn canonicalize(n: u64) -> u64 {
+        if n >= P {
+            n - P
+        } else {
+            n
+        }
+    }
+
+    /// reduces a 128-bit product using 2^64 = 2^32 - 1 and 2^96 = -1 (mod p).
+    #[inline(always)]
+    fn reduce128(x: u128) -> u64 {
+        let x_lo = x as u64;
+        let x_hi = (x >> 64) as u64;
+        let x_hi_hi = x_hi >> 32;
+        let x_hi_lo = x_hi & EPSILON;
+
+        let (mut t0, borrow) = x_lo.overflowing_sub(x_hi_hi);
+        if borrow {
+            // wrapped around 2^64, bring it back by adding p
+            t0 = t0.wrapping_sub(EPSILON);
+        }
+        let t1 = x_hi_lo * EPSILON;
+        let (res, carry) = t0.overflowing_add(t1);
+        Self::canonicalize(res.wrapping_add(EPSILON * carry as u64))
+    }
+}
+
+impl Add for Goldilocks {
+    type Output = Self;
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        let (sum, carry) = self.0.overflowing_add(other.0);
+        if carry {
+            // sum wrapped past 2^64 = EPSILON (mod p)
+            Self(Self::canonicalize(sum.wrapping_add(EPSILON)))
+        } else {
+            Self(Self::canonicalize(sum))
+        }
+    }
+}
+
+impl Sub for Goldilocks {
+    type Output = Self;
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        let (diff, borrow) = self.0.overflowing_sub(other.0);
+        if borrow {
+            Self(diff.wrapping_sub(EPSILON))
+        } else {
+            Self(diff)
+        }
+    }
+}
+
+impl Mul for Goldilocks {
+    type Output = Self;
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        Self(Self::reduce128(self.0 as u128 * other.0 as u128))
+    }
+}
+
+impl Div for Goldilocks {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inverse()
+    }
+}
+
+impl From<u64> for Goldilocks {
+    fn from(n: u64) -> Self {
+        Self::new(n)
+    }
+}
+
+impl fmt::Display for Goldilocks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Field for Goldilocks {
+    const TWO_ADICITY: u32 = 32;
+    const NUM_BYTES: usize = 8;
+
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn one() -> Self {
+        Self::one()
+    }
+
+    fn from_u128(n: u128) -> Self {
+        Self((n % P as u128) as u64)
+    }
+
+    fn inverse(&self) -> Self {
+        self.inverse()
+    }
+
+    fn two_adic_generator() -> Self {
+        // 7^((p - 1) / 2^32)
+        Self(1753635133440165772)
+    }
+
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        self.0.to_le_bytes().to_vec()
+    }
+
+    fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let arr: [u8; 8] = bytes.try_into().map_err(|_| Error::InvalidLength)?;
+        let n = u64::from_le_bytes(arr);
+        if n >= P {
+            return Err(Error::NonCanonical);
+        }
+        Ok(Self(n))
+    }
+}
+
+impl Mul<FFPoly<Goldilocks>> for Goldilocks {
+    type Output = FFPoly<Goldilocks>;
+
+    fn mul(self, other: FFPoly<Goldilocks>) -> FFPoly<Goldilocks> {
+        other.scalar_mul(&self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::Channel;
+    use crate::merkle::MerkleTree;
+    use crate::FFPoly::interpolate_poly;
+
+    fn reference_mul(a: u64, b: u64) -> u64 {
+        (a as u128 * b as u128 % P as u128) as u64
+    }
+
+    #[test]
+    fn test_arithmetic_matches_u128_reference() {
+        let vals = [
+            0,
+            1,
+            2,
+            EPSILON,
+            EPSILON + 1,
+            P - 1,
+            P - 2,
+            1 << 63,
+            0x1234_5678_9abc_def0,
+        ];
+        for &a in vals.iter() {
+            for &b in vals.iter() {
+                let (x, y) = (Goldilocks::new(a), Goldilocks::new(b));
+                assert_eq!((x * y).inner(), reference_mul(a % P, b % P));
+                assert_eq!(
+                    (x + y).inner(),
+                    ((a % P) as u128 + (b % P) as u128).rem_euclid(P as u128) as u64
+                );
+                assert_eq!(
+                    (x - y).inner(),
+                    ((a % P) as i128 - (b % P) as i128).rem_euclid(P as i128) as u64
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        let x = Goldilocks::new(0xdead_beef_cafe_babe);
+        assert_eq!(x * x.inverse(), Goldilocks::one());
+        assert_eq!(Goldilocks::new(P), Goldilocks::zero());
+    }
+
+    #[test]
+    fn test_two_adic_generator() {
+        let g = Goldilocks::two_adic_generator();
+        assert_eq!(g, Goldilocks::pow(Goldilocks::new(7), (P - 1) >> 32));
+        assert_eq!(Goldilocks::pow(g, 1 << 32), Goldilocks::one());
+        assert_eq!(Goldilocks::pow(g, 1 << 31), Goldilocks::new(P - 1));
+    }
+
+    #[test]
+    fn test_works_with_poly_merkle_and_channel() {
+        let xs: Vec<Goldilocks> = (1..5).map(Goldilocks::new).collect();
+        let ys: Vec<Goldilocks> = xs
+            .iter()
+            .map(|x| *x * *x * *x + Goldilocks::one())
+            .collect();
+        let f = interpolate_poly(&xs, &ys, "x");
+        assert_eq!(f.degree(), 3);
+        assert_eq!(f.eval(&Goldilocks::new(10)), Goldilocks::new(1001));
+
+        let mut tree = MerkleTree::new(&ys);
+        tree.build_tree();
+        assert_eq!(tree.num_of_leaves(), 4);
+
+        let mut chan = Channel::new();
+        chan.send(&tree.root());
+        let r: Goldilocks = chan.receive_rnd_field();
+        assert!(r.inner() < P);
+        assert_eq!(r.to_canonical_bytes().len(), Goldilocks::NUM_BYTES);
+        assert_eq!(
+            Goldilocks::from_canonical_bytes(&r.to_canonical_bytes()),
+            Ok(r)
+        );
+        assert_eq!(
+            Goldilocks::from_canonical_bytes(&P.to_le_bytes()),
+            Err(Error::NonCanonical)
+        );
+    }
+}
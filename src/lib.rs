@@ -1,8 +1,24 @@
-mod FFPoly;
-mod channel;
-mod ffield_unit;
-mod merkle;
-mod utils;
+#[allow(non_snake_case)]
+pub mod FFPoly;
+pub mod barycentric;
+pub mod channel;
+pub mod domain;
+pub mod error;
+pub mod evaluations;
+pub mod extension;
+pub mod ffield_unit;
+pub mod field;
+pub mod fp;
+pub mod goldilocks;
+pub mod merkle;
+mod montgomery;
+pub mod multivariate;
+pub mod ntt;
+pub mod packed;
+pub mod sparse;
+pub mod stark252;
+pub mod subproduct;
+pub mod utils;
 
 #[cfg(feature = "examples")]
 mod fibonacci_squares;
@@ -1,4 +1,4 @@
-use crate::{ffield_unit::FFieldUnit, utils::hash256_str};
+use crate::{ffield_unit::FFieldUnit, field::Field, utils::hash256_str};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
@@ -14,26 +14,34 @@ impl Node {
             leaf_data: ld,
         }
     }
+
+    pub fn leaf_data(&self) -> Option<&String> {
+        self.leaf_data.as_ref()
+    }
+
+    pub fn children(&self) -> Option<&(String, String)> {
+        self.children.as_ref()
+    }
 }
 
 #[derive(Clone, Debug)]
-pub struct MerkleTree {
+pub struct MerkleTree<F: Field = FFieldUnit> {
     root: String,
     height: usize,
     num_of_leaves: usize,
-    data: Vec<FFieldUnit>,
+    data: Vec<F>,
     facts: HashMap<String, Node>,
 }
 
-impl MerkleTree {
-    pub fn new(data: &[FFieldUnit]) -> Self {
+impl<F: Field> MerkleTree<F> {
+    pub fn new(data: &[F]) -> Self {
         let len = data.len();
         let num_of_leaves = 2_usize.pow((len as f32).log2().ceil() as u32);
         let height = (num_of_leaves as f32).log2() as usize;
 
         // pad with zeroes
-        let mut new_data = Vec::<FFieldUnit>::from(data);
-        new_data.extend(std::iter::repeat(FFieldUnit::zero()).take(num_of_leaves - len));
+        let mut new_data = Vec::<F>::from(data);
+        new_data.extend(std::iter::repeat_n(F::zero(), num_of_leaves - len));
 
         Self {
             height,
@@ -68,10 +76,11 @@ impl MerkleTree {
         let len = self.data.len();
 
         if node_id >= len {
-            // a leaf
+            // a leaf. its data is the decimal string of the element, whose formatting
+            // outweighs the single Montgomery reduction a packed kernel could batch
             let id_in_data = node_id - len;
             let leaf_data = self.data[id_in_data].to_string();
-            let h = hash256_str(&leaf_data.as_bytes());
+            let h = hash256_str(leaf_data.as_bytes());
             self.facts
                 .insert(h.clone(), Node::new(Some(leaf_data), None));
             return h;
@@ -80,10 +89,10 @@ impl MerkleTree {
         let left = self.recursive_build_tree(node_id * 2);
         let right = self.recursive_build_tree(node_id * 2 + 1);
 
-        let h = hash256_str(&(left.clone() + &right).as_bytes());
+        let h = hash256_str((left.clone() + &right).as_bytes());
         self.facts
             .insert(h.clone(), Node::new(None, Some((left, right))));
-        return h;
+        h
     }
 }
 
@@ -108,7 +117,7 @@ mod tests {
             "3ae3c7b1e8a3d8619bf179da070f5ed9270ac7191ff61a04285981e24225b3db",
         ];
         for i in 0..10 {
-            let units = get_ffunits_in_range(0 + i, 8192 + i);
+            let units = get_ffunits_in_range(i, 8192 + i);
             let mut merkle = MerkleTree::new(&units);
             merkle.build_tree();
             assert_eq!(merkle.facts().len(), 16383);
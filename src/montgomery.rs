@@ -0,0 +1,96 @@
+//! Montgomery arithmetic helpers for odd moduli below 2^32, with R = 2^32.
+//! A value x is stored as x * R mod p, which turns modular multiplication into
+//! one 64-bit product followed by a shift-and-subtract reduction.
+
+/// p^-1 mod 2^32 via Newton iteration, p must be odd.
+pub(crate) const fn inv_mod_2_32(p: u32) -> u32 {
+    // correct to 3 bits for any odd p, every step doubles the precision
+    let mut inv = p;
+    let mut i = 0;
+    while i < 4 {
+        inv = inv.wrapping_mul(2u32.wrapping_sub(p.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv
+}
+
+/// R^2 mod p, used to move values into Montgomery form.
+pub(crate) const fn r_squared(p: u32) -> u32 {
+    ((1u128 << 64) % p as u128) as u32
+}
+
+/// R mod p, the Montgomery form of one.
+pub(crate) const fn r_mod(p: u32) -> u32 {
+    ((1u64 << 32) % p as u64) as u32
+}
+
+/// t * R^-1 mod p for t < p * 2^32, result in [0, p).
+#[inline(always)]
+pub(crate) const fn reduce(t: u64, p: u32, p_inv: u32) -> u32 {
+    // m * p agrees with t on the low 32 bits, so t - m * p is an exact multiple of R
+    let m = (t as u32).wrapping_mul(p_inv);
+    let mp = m as u64 * p as u64;
+    let (hi, borrow) = ((t >> 32) as u32).overflowing_sub((mp >> 32) as u32);
+    if borrow {
+        hi.wrapping_add(p)
+    } else {
+        hi
+    }
+}
+
+#[inline(always)]
+pub(crate) const fn mul(a: u32, b: u32, p: u32, p_inv: u32) -> u32 {
+    reduce(a as u64 * b as u64, p, p_inv)
+}
+
+#[inline(always)]
+pub(crate) const fn add(a: u32, b: u32, p: u32) -> u32 {
+    let s = a as u64 + b as u64;
+    if s >= p as u64 {
+        (s - p as u64) as u32
+    } else {
+        s as u32
+    }
+}
+
+#[inline(always)]
+pub(crate) const fn sub(a: u32, b: u32, p: u32) -> u32 {
+    let (d, borrow) = a.overflowing_sub(b);
+    if borrow {
+        d.wrapping_add(p)
+    } else {
+        d
+    }
+}
+
+/// canonical x < p into Montgomery form.
+#[inline(always)]
+pub(crate) const fn to_monty(x: u32, p: u32, p_inv: u32) -> u32 {
+    mul(x, r_squared(p), p, p_inv)
+}
+
+/// Montgomery form back to the canonical representative.
+#[inline(always)]
+pub(crate) const fn from_monty(x: u32, p: u32, p_inv: u32) -> u32 {
+    reduce(x as u64, p, p_inv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: u32 = 3 * (1 << 30) + 1;
+
+    #[test]
+    fn test_round_trip_and_mul() {
+        let p_inv = inv_mod_2_32(P);
+        assert_eq!(P.wrapping_mul(p_inv), 1);
+        for (a, b) in [(0u32, 5u32), (1, 1), (P - 1, P - 1), (123456789, 987654321)] {
+            let (ma, mb) = (to_monty(a, P, p_inv), to_monty(b, P, p_inv));
+            assert_eq!(from_monty(ma, P, p_inv), a);
+            let prod = from_monty(mul(ma, mb, P, p_inv), P, p_inv);
+            assert_eq!(prod as u64, a as u64 * b as u64 % P as u64);
+        }
+        assert_eq!(to_monty(1, P, p_inv), r_mod(P));
+    }
+}
@@ -0,0 +1,294 @@
+use crate::ffield_unit::FFieldUnit;
+use crate::field::Field;
+use crate::FFPoly::FFPoly;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// sparse polynomial in num_vars indexed variables x0, x1, ..., stored as a map from
+/// exponent vectors to non-zero coefficients. meant for AIR constraints such as
+/// x2 - x1^2 - x0^2, which `substitute` turns into univariate composition polynomials.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiPoly<F: Field = FFieldUnit> {
+    num_vars: usize,
+    terms: BTreeMap<Vec<u32>, F>,
+}
+
+impl<F: Field> MultiPoly<F> {
+    /// terms as (exponents, coeff) pairs, one exponent per variable; repeated exponent
+    /// vectors are added up and zero coefficients dropped.
+    pub fn new(terms: Vec<(Vec<u32>, F)>, num_vars: usize) -> Self {
+        let mut res = Self::zero(num_vars);
+        for (exps, c) in terms {
+            res.add_term(exps, c);
+        }
+        res
+    }
+
+    pub fn zero(num_vars: usize) -> Self {
+        Self {
+            num_vars,
+            terms: BTreeMap::new(),
+        }
+    }
+
+    pub fn constant(c: F, num_vars: usize) -> Self {
+        Self::new(vec![(vec![0; num_vars], c)], num_vars)
+    }
+
+    /// the variable x_i.
+    pub fn var(i: usize, num_vars: usize) -> Self {
+        assert!(i < num_vars, "variable index out of range");
+        let mut exps = vec![0; num_vars];
+        exps[i] = 1;
+        Self::new(vec![(exps, F::one())], num_vars)
+    }
+
+    fn add_term(&mut self, exps: Vec<u32>, c: F) {
+        assert_eq!(exps.len(), self.num_vars, "one exponent per variable");
+        let sum = self.terms.get(&exps).map_or(c, |old| *old + c);
+        if sum.is_zero() {
+            self.terms.remove(&exps);
+        } else {
+            self.terms.insert(exps, sum);
+        }
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    pub fn terms(&self) -> impl Iterator<Item = (&[u32], &F)> {
+        self.terms.iter().map(|(e, c)| (e.as_slice(), c))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// highest exponent of x_i over all terms, 0 for i >= num_vars as no term contains
+    /// such a variable.
+    pub fn degree_in(&self, i: usize) -> u32 {
+        if i >= self.num_vars {
+            return 0;
+        }
+        self.terms.keys().map(|e| e[i]).max().unwrap_or(0)
+    }
+
+    /// highest sum of exponents over all terms.
+    pub fn total_degree(&self) -> u32 {
+        self.terms.keys().map(|e| e.iter().sum()).max().unwrap_or(0)
+    }
+
+    pub fn eval(&self, point: &[F]) -> F {
+        assert_eq!(point.len(), self.num_vars, "one value per variable");
+        self.terms.iter().fold(F::zero(), |acc, (exps, c)| {
+            let monomial = exps
+                .iter()
+                .zip(point)
+                .fold(*c, |m, (e, x)| m * x.pow(*e as u64));
+            acc + monomial
+        })
+    }
+
+    /// replaces x_i by polys[i] and expands, e.g. x0 -> f(x), x1 -> f(gx),
+    /// x2 -> f(g^2 x) for a transition constraint over a trace polynomial f.
+    /// the powers of every polys[i] are computed once and shared between terms.
+    pub fn substitute(&self, polys: &[FFPoly<F>], var: &str) -> FFPoly<F> {
+        assert_eq!(polys.len(), self.num_vars, "one polynomial per variable");
+        let one = FFPoly::new(vec![F::one()], var);
+        let powers: Vec<Vec<FFPoly<F>>> = polys
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let mut pows = vec![one.clone()];
+                for _ in 0..self.degree_in(i) {
+                    let next = pows.last().unwrap().clone() * p.clone();
+                    pows.push(next);
+                }
+                pows
+            })
+            .collect();
+
+        self.terms
+            .iter()
+            .fold(FFPoly::new(vec![], var), |acc, (exps, c)| {
+                let monomial = exps
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, e)| **e > 0)
+                    .fold(one.scalar_mul(c), |m, (i, e)| {
+                        m * powers[i][*e as usize].clone()
+                    });
+                acc + monomial
+            })
+    }
+
+    pub fn scalar_mul(&self, v: &F) -> Self {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(e, c)| (e.clone(), *c * *v))
+            .collect();
+        Self::new(terms, self.num_vars)
+    }
+
+    pub fn pow(&self, mut e: u32) -> Self {
+        let mut base = self.clone();
+        let mut res = Self::constant(F::one(), self.num_vars);
+        while e > 0 {
+            if e & 1 == 1 {
+                res = res * base.clone();
+            }
+            e >>= 1;
+            if e > 0 {
+                base = base.clone() * base;
+            }
+        }
+        res
+    }
+}
+
+impl<F: Field> Add for MultiPoly<F> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        assert_eq!(
+            self.num_vars, other.num_vars,
+            "different number of variables"
+        );
+        for (exps, c) in other.terms {
+            self.add_term(exps, c);
+        }
+        self
+    }
+}
+
+impl<F: Field> Neg for MultiPoly<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.scalar_mul(&F::one().neg())
+    }
+}
+
+impl<F: Field> Sub for MultiPoly<F> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl<F: Field> Mul for MultiPoly<F> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        assert_eq!(
+            self.num_vars, other.num_vars,
+            "different number of variables"
+        );
+        let mut res = Self::zero(self.num_vars);
+        for (e1, c1) in self.terms.iter() {
+            for (e2, c2) in other.terms.iter() {
+                let exps = e1.iter().zip(e2).map(|(a, b)| a + b).collect();
+                res.add_term(exps, *c1 * *c2);
+            }
+        }
+        res
+    }
+}
+
+impl<F: Field> Mul<F> for MultiPoly<F> {
+    type Output = Self;
+
+    fn mul(self, scalar: F) -> Self {
+        self.scalar_mul(&scalar)
+    }
+}
+
+/// terms in descending order of their exponent vectors, coefficient first, e.g.
+/// "3*x0^2*x1 + 1*x2".
+impl<F: Field> fmt::Display for MultiPoly<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+        let terms: Vec<String> = self
+            .terms
+            .iter()
+            .rev()
+            .map(|(exps, c)| {
+                let vars: String = exps
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, e)| **e > 0)
+                    .map(|(i, e)| match e {
+                        1 => format!("*x{}", i),
+                        _ => format!("*x{}^{}", i, e),
+                    })
+                    .collect();
+                format!("{}{}", c, vars)
+            })
+            .collect();
+        write!(f, "{}", terms.join(" + "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::get_ffunits_in_range;
+
+    fn fib_sq_transition() -> MultiPoly {
+        let x = |i| MultiPoly::var(i, 3);
+        x(2) - x(1).pow(2) - x(0).pow(2)
+    }
+
+    #[test]
+    fn test_degrees_and_eval() {
+        let c = fib_sq_transition();
+        assert_eq!(c.degree_in(0), 2);
+        assert_eq!(c.degree_in(2), 1);
+        assert_eq!(c.degree_in(3), 0);
+        assert_eq!(c.total_degree(), 2);
+        let (a, b) = (FFieldUnit::new(3), FFieldUnit::new(4));
+        assert!(c.eval(&[a, b, a * a + b * b]).is_zero());
+        assert_eq!(c.eval(&[a, b, FFieldUnit::one()]), FFieldUnit::new(-24));
+
+        let mixed = (MultiPoly::<FFieldUnit>::var(0, 2) * MultiPoly::var(1, 2)).pow(3);
+        assert_eq!(mixed.total_degree(), 6);
+        assert_eq!(mixed.degree_in(1), 3);
+    }
+
+    #[test]
+    fn test_arithmetic_normalizes() {
+        let x0 = MultiPoly::<FFieldUnit>::var(0, 2);
+        let x1 = MultiPoly::var(1, 2);
+        assert!((x0.clone() - x0.clone()).is_zero());
+        assert_eq!(x0.clone() + x1.clone() - x0.clone(), x1.clone());
+        let square = (x0.clone() + x1.clone()) * (x0.clone() + x1.clone());
+        let expanded = x0.pow(2) + x0.clone() * x1.clone() * FFieldUnit::new(2) + x1.pow(2);
+        assert_eq!(square, expanded);
+        assert_eq!(square.terms().count(), 3);
+        assert_eq!(format!("{}", x0 + x1 * FFieldUnit::new(5)), "1*x0 + 5*x1");
+    }
+
+    #[test]
+    fn test_substitution_builds_composition_polynomial() {
+        // the FibonacciSq transition as assembled by hand in part_two
+        let f = FFPoly::new(get_ffunits_in_range(-6, 10), "x");
+        let g = FFieldUnit::generator();
+        let f_g = f.compose(&FFPoly::new(vec![FFieldUnit::zero(), g], "x"));
+        let f_g2 = f.compose(&FFPoly::new(vec![FFieldUnit::zero(), g * g], "x"));
+        let expected = f_g2.clone() - f_g.clone() * f_g.clone() - f.clone() * f.clone();
+
+        let numer = fib_sq_transition().substitute(&[f.clone(), f_g, f_g2], "x");
+        assert_eq!(numer, expected);
+        let z = FFieldUnit::new(777);
+        assert_eq!(
+            numer.eval(&z),
+            fib_sq_transition().eval(&[f.eval(&z), f.eval(&(g * z)), f.eval(&(g * g * z))])
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use crate::domain::Domain;
+use crate::field::Field;
+use crate::FFPoly::FFPoly;
+
+/// reorders vals so that index i moves to the bit reversal of i (log2(len) bits).
+pub fn bit_reverse_permute<F>(vals: &mut [F]) {
+    let n = vals.len();
+    assert!(n.is_power_of_two(), "length must be a power of two");
+    let log_n = n.trailing_zeros();
+    if log_n == 0 {
+        return;
+    }
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            vals.swap(i, j);
+        }
+    }
+}
+
+/// true for the sizes the NTT handles: 3^a * 2^k.
+pub fn is_ntt_size(mut n: usize) -> bool {
+    if n == 0 {
+        return false;
+    }
+    while n.is_multiple_of(3) {
+        n /= 3;
+    }
+    n.is_power_of_two()
+}
+
+/// replaces the coefficients in vals with the evaluations at root^0, ..., root^(n-1).
+/// root must be a primitive n-th root of unity and n = 3^a * 2^k.
+pub fn ntt_with_root<F: Field>(vals: &mut [F], root: F) {
+    assert!(is_ntt_size(vals.len()), "length must be 3^a * 2^k");
+    if vals.len().is_power_of_two() {
+        radix2_ntt(vals, root);
+    } else {
+        radix3_ntt(vals, root);
+    }
+}
+
+/// one radix-3 step: with f(x) = f0(x^3) + x f1(x^3) + x^2 f2(x^3), each f_j is
+/// evaluated over the subgroup of order n / 3 generated by root^3, and
+/// f(root^i) = F0[i'] + root^i F1[i'] + root^2i F2[i'] with i' = i mod n / 3.
+fn radix3_ntt<F: Field>(vals: &mut [F], root: F) {
+    let m = vals.len() / 3;
+    let root_cubed = root * root * root;
+    let mut parts: Vec<Vec<F>> = (0..3)
+        .map(|j| vals.iter().skip(j).step_by(3).copied().collect())
+        .collect();
+    for part in parts.iter_mut() {
+        ntt_with_root(part, root_cubed);
+    }
+
+    let mut w = F::one();
+    for (i, v) in vals.iter_mut().enumerate() {
+        let k = i % m;
+        *v = parts[0][k] + w * (parts[1][k] + w * parts[2][k]);
+        w = w * root;
+    }
+}
+
+/// iterative Cooley-Tukey: bit-reverse the input, then log2(n) rounds of butterflies
+/// with twiddles taken from a single table of root powers.
+fn radix2_ntt<F: Field>(vals: &mut [F], root: F) {
+    let n = vals.len();
+    bit_reverse_permute(vals);
+    if n == 1 {
+        return;
+    }
+
+    let mut twiddles = Vec::with_capacity(n / 2);
+    let mut w = F::one();
+    for _ in 0..n / 2 {
+        twiddles.push(w);
+        w = w * root;
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let stride = n / len;
+        for chunk in vals.chunks_exact_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            for (k, (a, b)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                let t = *b * twiddles[k * stride];
+                *b = *a - t;
+                *a = *a + t;
+            }
+        }
+        len *= 2;
+    }
+}
+
+/// inverse of `ntt_with_root`: evaluations at root^i back to coefficients.
+pub fn intt_with_root<F: Field>(vals: &mut [F], root: F) {
+    let n = vals.len();
+    ntt_with_root(vals, root.inverse());
+    let n_inv = F::from_u128(n as u128).inverse();
+    for v in vals.iter_mut() {
+        *v = *v * n_inv;
+    }
+}
+
+/// forward NTT over the subgroup of order vals.len() generated by
+/// `F::root_of_unity_of_order`.
+pub fn ntt<F: Field>(vals: &mut [F]) {
+    ntt_with_root(vals, subgroup_root(vals.len()));
+}
+
+/// inverse NTT over the subgroup of order vals.len() generated by
+/// `F::root_of_unity_of_order`.
+pub fn intt<F: Field>(vals: &mut [F]) {
+    intt_with_root(vals, subgroup_root(vals.len()));
+}
+
+fn subgroup_root<F: Field>(n: usize) -> F {
+    F::root_of_unity_of_order(n as u64).expect("field has no subgroup of this size")
+}
+
+/// the generator g when x_vals is exactly 1, g, g^2, ..., g^(n-1) for g of order
+/// n = x_vals.len() an NTT size, i.e. a full subgroup in natural order.
+pub fn subgroup_generator<F: Field>(x_vals: &[F]) -> Option<F> {
+    let n = x_vals.len();
+    if !is_ntt_size(n) || x_vals[0] != F::one() {
+        return None;
+    }
+    if n == 1 {
+        return Some(F::one());
+    }
+    let g = x_vals[1];
+    let is_chain = x_vals.windows(2).all(|w| w[1] == w[0] * g);
+    // g^n = 1 and g^(n/q) != 1 for the primes q | n pins the order of g to exactly n
+    let is_primitive = [2, 3]
+        .iter()
+        .filter(|q| n.is_multiple_of(**q))
+        .all(|q| x_vals[n / q] != F::one());
+    if is_chain && x_vals[n - 1] * g == F::one() && is_primitive {
+        Some(g)
+    } else {
+        None
+    }
+}
+
+/// coefficients of a * b through a size 2^k NTT, None if the field has no subgroup
+/// large enough for the product.
+pub fn convolve<F: Field>(a: &[F], b: &[F]) -> Option<Vec<F>> {
+    if a.is_empty() || b.is_empty() {
+        return Some(vec![]);
+    }
+    let res_len = a.len() + b.len() - 1;
+    let n = res_len.next_power_of_two();
+    let root = F::root_of_unity(n.trailing_zeros())?;
+
+    let mut fa = a.to_vec();
+    fa.resize(n, F::zero());
+    let mut fb = b.to_vec();
+    fb.resize(n, F::zero());
+    ntt_with_root(&mut fa, root);
+    ntt_with_root(&mut fb, root);
+    for (x, y) in fa.iter_mut().zip(fb) {
+        *x = *x * y;
+    }
+    intt_with_root(&mut fa, root);
+    fa.truncate(res_len);
+    Some(fa)
+}
+
+/// low-degree extension of a trace: trace[i] is the value at g^i on the subgroup of
+/// order trace.len(), the result holds the values of the same polynomial on the
+/// coset offset * H with |H| = blowup * trace.len().
+pub fn lde_from_evaluations<F: Field>(trace: &[F], blowup: usize, offset: F) -> Vec<F> {
+    let n = trace.len();
+    let mut coeffs = trace.to_vec();
+    intt_with_root(&mut coeffs, subgroup_root(n));
+    coset_ntt(coeffs, n * blowup, offset)
+}
+
+/// evaluates the coefficients on offset * H with |H| = size.
+fn coset_ntt<F: Field>(coeffs: Vec<F>, size: usize, offset: F) -> Vec<F> {
+    let domain = Domain::coset_of_size(size, offset).expect("field has no subgroup of this size");
+    FFPoly::new_no_trim(coeffs, "x").evaluate_over(&domain)
+}
+
+impl<F: Field> FFPoly<F> {
+    /// values on the coset offset * H, where |H| is blowup times the trace domain:
+    /// the smallest power of two holding all the coefficients.
+    pub fn lde(&self, blowup: usize, offset: F) -> Vec<F> {
+        let trace_size = self.coeffs.len().max(1).next_power_of_two();
+        coset_ntt(self.coeffs.clone(), trace_size * blowup, offset)
+    }
+
+    /// evaluates on every point of domain, in the domain's natural order.
+    /// coefficients beyond the domain size wrap around (x^n = offset^n on the coset).
+    pub fn evaluate_over(&self, domain: &Domain<F>) -> Vec<F> {
+        let n = domain.size();
+        let mut vals = vec![F::zero(); n];
+        let mut shift = F::one();
+        for (i, c) in self.coeffs.iter().enumerate() {
+            vals[i % n] = vals[i % n] + *c * shift;
+            shift = shift * domain.offset();
+        }
+        ntt_with_root(&mut vals, domain.generator());
+        vals
+    }
+
+    /// the unique polynomial of degree < domain.size() taking the given values on domain.
+    pub fn interpolate_over(domain: &Domain<F>, evals: &[F], var: &str) -> Self {
+        assert_eq!(evals.len(), domain.size(), "one value per domain point");
+        let mut coeffs = evals.to_vec();
+        intt_with_root(&mut coeffs, domain.generator());
+        let offset_inv = domain.offset().inverse();
+        let mut shift = F::one();
+        for c in coeffs.iter_mut() {
+            *c = *c * shift;
+            shift = shift * offset_inv;
+        }
+        Self::new(coeffs, var)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffield_unit::FFieldUnit;
+    use crate::goldilocks::Goldilocks;
+    use crate::utils::get_ffunits_in_range;
+
+    #[test]
+    fn test_ntt_matches_naive_eval() {
+        let coeffs = get_ffunits_in_range(-7, 25);
+        let f = FFPoly::new(coeffs.clone(), "x");
+        let mut vals = coeffs;
+        ntt(&mut vals);
+        let dom = Domain::<FFieldUnit>::subgroup(5).unwrap();
+        for (x, v) in dom.iter().zip(vals.iter()) {
+            assert_eq!(f.eval(&x), *v);
+        }
+        intt(&mut vals);
+        assert_eq!(vals, f.coeffs);
+    }
+
+    #[test]
+    fn test_evaluate_over_coset_and_back() {
+        let f = FFPoly::new(get_ffunits_in_range(1, 100), "x");
+        let dom = Domain::coset(8, FFieldUnit::ffgenerator()).unwrap();
+        let evals = f.evaluate_over(&dom);
+        assert_eq!(evals, f.eval_batch(&dom.elements()));
+        assert_eq!(FFPoly::interpolate_over(&dom, &evals, "x"), f);
+
+        // more coefficients than points still evaluates correctly
+        let small = Domain::coset(4, FFieldUnit::ffgenerator()).unwrap();
+        assert_eq!(f.evaluate_over(&small), f.eval_batch(&small.elements()));
+    }
+
+    #[test]
+    fn test_mixed_radix_ntt() {
+        // 96 = 3 * 2^5
+        let coeffs = get_ffunits_in_range(-40, 56);
+        let f = FFPoly::new(coeffs.clone(), "x");
+        let mut vals = coeffs.clone();
+        ntt(&mut vals);
+        let dom = Domain::<FFieldUnit>::subgroup_of_size(96).unwrap();
+        assert_eq!(vals, f.eval_batch(&dom.elements()));
+        intt(&mut vals);
+        assert_eq!(vals, coeffs);
+
+        let coset = Domain::coset_of_size(3 * 1024, FFieldUnit::ffgenerator()).unwrap();
+        let evals = f.evaluate_over(&coset);
+        assert_eq!(evals, f.eval_batch(&coset.elements()));
+        assert_eq!(FFPoly::interpolate_over(&coset, &evals, "x"), f);
+        let xs = coset
+            .elements()
+            .iter()
+            .map(|x| *x / coset.offset())
+            .collect::<Vec<_>>();
+        assert_eq!(subgroup_generator(&xs), Some(coset.generator()));
+    }
+
+    #[test]
+    fn test_lde() {
+        let w = FFieldUnit::ffgenerator();
+        let trace = get_ffunits_in_range(100, 132);
+        let xs = Domain::<FFieldUnit>::subgroup(5).unwrap().elements();
+        let f = crate::FFPoly::interpolate_poly(&xs, &trace, "x");
+
+        let coset = Domain::coset(7, w).unwrap();
+        let expected = f.eval_batch(&coset.elements());
+        assert_eq!(f.lde(4, w), expected);
+        assert_eq!(lde_from_evaluations(&trace, 4, w), expected);
+
+        // blowup 3 lands on a mixed-radix coset
+        let coset = Domain::coset_of_size(96, w).unwrap();
+        assert_eq!(f.lde(3, w), f.eval_batch(&coset.elements()));
+    }
+
+    #[test]
+    fn test_subgroup_detection() {
+        let dom = Domain::<Goldilocks>::subgroup(4).unwrap();
+        let elems = dom.elements();
+        assert_eq!(subgroup_generator(&elems), Some(dom.generator()));
+        assert_eq!(subgroup_generator(&elems[..15]), None);
+        let mut swapped = elems.clone();
+        swapped.swap(3, 4);
+        assert_eq!(subgroup_generator(&swapped), None);
+        let coset = Domain::coset(4, Goldilocks::new(7)).unwrap();
+        assert_eq!(subgroup_generator(&coset.elements()), None);
+    }
+
+    #[test]
+    fn test_bit_reverse_permute() {
+        let mut v: Vec<usize> = (0..8).collect();
+        bit_reverse_permute(&mut v);
+        assert_eq!(v, vec![0, 4, 2, 6, 1, 5, 3, 7]);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::ffield_unit::FFieldUnit;
+use crate::FFPoly::FFPoly;
+use std::ops::{Add, Mul, Sub};
+
+/// number of lanes in a PackedFFieldUnit. 8 x u32 fills a 256-bit register.
+pub const WIDTH: usize = 8;
+
+/// WIDTH field elements operated on lane by lane.
+/// every op is a straight-line loop over a fixed-size array of Montgomery u32s,
+/// which the compiler turns into SIMD on whatever the target offers (SSE2 is
+/// enough on x86-64); there is no architecture specific code here.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PackedFFieldUnit(pub [FFieldUnit; WIDTH]);
+
+impl PackedFFieldUnit {
+    pub fn broadcast(x: FFieldUnit) -> Self {
+        Self([x; WIDTH])
+    }
+
+    pub fn zero() -> Self {
+        Self::broadcast(FFieldUnit::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::broadcast(FFieldUnit::one())
+    }
+
+    /// the first WIDTH elements of vals.
+    pub fn from_slice(vals: &[FFieldUnit]) -> Self {
+        let mut lanes = [FFieldUnit::zero(); WIDTH];
+        lanes.copy_from_slice(&vals[..WIDTH]);
+        Self(lanes)
+    }
+
+    pub fn write_to_slice(&self, out: &mut [FFieldUnit]) {
+        out[..WIDTH].copy_from_slice(&self.0);
+    }
+}
+
+impl Add for PackedFFieldUnit {
+    type Output = Self;
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        let mut res = self.0;
+        for (r, o) in res.iter_mut().zip(other.0.iter()) {
+            *r += *o;
+        }
+        Self(res)
+    }
+}
+
+impl Sub for PackedFFieldUnit {
+    type Output = Self;
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        let mut res = self.0;
+        for (r, o) in res.iter_mut().zip(other.0.iter()) {
+            *r -= *o;
+        }
+        Self(res)
+    }
+}
+
+impl Mul for PackedFFieldUnit {
+    type Output = Self;
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        let mut res = self.0;
+        for (r, o) in res.iter_mut().zip(other.0.iter()) {
+            *r *= *o;
+        }
+        Self(res)
+    }
+}
+
+impl Mul<FFieldUnit> for PackedFFieldUnit {
+    type Output = Self;
+    #[inline]
+    fn mul(self, other: FFieldUnit) -> Self {
+        self * Self::broadcast(other)
+    }
+}
+
+/// applies op to WIDTH-sized chunks of (a, b) packed, then to the tail one by one.
+#[inline(always)]
+fn zip_apply(
+    a: &mut [FFieldUnit],
+    b: &[FFieldUnit],
+    packed_op: impl Fn(PackedFFieldUnit, PackedFFieldUnit) -> PackedFFieldUnit,
+    op: impl Fn(FFieldUnit, FFieldUnit) -> FFieldUnit,
+) {
+    assert_eq!(a.len(), b.len(), "slices must have equal length");
+    let mut a_chunks = a.chunks_exact_mut(WIDTH);
+    let mut b_chunks = b.chunks_exact(WIDTH);
+    for (ac, bc) in (&mut a_chunks).zip(&mut b_chunks) {
+        packed_op(
+            PackedFFieldUnit::from_slice(ac),
+            PackedFFieldUnit::from_slice(bc),
+        )
+        .write_to_slice(ac);
+    }
+    for (x, y) in a_chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(b_chunks.remainder())
+    {
+        *x = op(*x, *y);
+    }
+}
+
+/// a[i] += b[i]
+pub fn add_assign_slices(a: &mut [FFieldUnit], b: &[FFieldUnit]) {
+    zip_apply(a, b, |x, y| x + y, |x, y| x + y);
+}
+
+/// a[i] -= b[i]
+pub fn sub_assign_slices(a: &mut [FFieldUnit], b: &[FFieldUnit]) {
+    zip_apply(a, b, |x, y| x - y, |x, y| x - y);
+}
+
+/// a[i] *= b[i]
+pub fn mul_assign_slices(a: &mut [FFieldUnit], b: &[FFieldUnit]) {
+    zip_apply(a, b, |x, y| x * y, |x, y| x * y);
+}
+
+/// y[i] += alpha * x[i]
+pub fn axpy(alpha: FFieldUnit, x: &[FFieldUnit], y: &mut [FFieldUnit]) {
+    let alpha_p = PackedFFieldUnit::broadcast(alpha);
+    zip_apply(y, x, |y, x| y + x * alpha_p, |y, x| y + x * alpha);
+}
+
+/// a[i] *= alpha
+pub fn scale_slice(a: &mut [FFieldUnit], alpha: FFieldUnit) {
+    let alpha_p = PackedFFieldUnit::broadcast(alpha);
+    let mut chunks = a.chunks_exact_mut(WIDTH);
+    for c in &mut chunks {
+        (PackedFFieldUnit::from_slice(c) * alpha_p).write_to_slice(c);
+    }
+    for x in chunks.into_remainder() {
+        *x *= alpha;
+    }
+}
+
+/// evaluates the polynomial with the given coefficients at every point, running
+/// Horner's rule across the points in lock step: out = out * points + c.
+pub fn horner_slices(coeffs: &[FFieldUnit], points: &[FFieldUnit], out: &mut [FFieldUnit]) {
+    assert_eq!(points.len(), out.len(), "slices must have equal length");
+    out.fill(FFieldUnit::zero());
+    for c in coeffs.iter().rev() {
+        let c_p = PackedFFieldUnit::broadcast(*c);
+        zip_apply(out, points, |acc, x| acc * x + c_p, |acc, x| acc * x + *c);
+    }
+}
+
+impl FFPoly<FFieldUnit> {
+    /// evaluates at every point using the packed Horner kernel, the FFieldUnit-only
+    /// counterpart of the Horner case of `multi_eval`.
+    pub fn eval_batch(&self, points: &[FFieldUnit]) -> Vec<FFieldUnit> {
+        let mut out = vec![FFieldUnit::zero(); points.len()];
+        horner_slices(&self.coeffs, points, &mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::get_ffunits_in_range;
+
+    #[test]
+    fn test_packed_ops_match_scalar() {
+        let a = get_ffunits_in_range(-4, 4);
+        let b = get_ffunits_in_range(100, 108);
+        let (pa, pb) = (
+            PackedFFieldUnit::from_slice(&a),
+            PackedFFieldUnit::from_slice(&b),
+        );
+        for i in 0..WIDTH {
+            assert_eq!((pa + pb).0[i], a[i] + b[i]);
+            assert_eq!((pa - pb).0[i], a[i] - b[i]);
+            assert_eq!((pa * pb).0[i], a[i] * b[i]);
+        }
+    }
+
+    #[test]
+    fn test_slice_kernels_handle_remainders() {
+        // 19 = 2 full chunks + 3 leftovers
+        let x = get_ffunits_in_range(1, 20);
+        let y0 = get_ffunits_in_range(-50, -31);
+        let alpha = FFieldUnit::new(7);
+
+        let mut y = y0.clone();
+        axpy(alpha, &x, &mut y);
+        for i in 0..19 {
+            assert_eq!(y[i], y0[i] + alpha * x[i]);
+        }
+
+        let mut z = y0.clone();
+        mul_assign_slices(&mut z, &x);
+        add_assign_slices(&mut z, &x);
+        sub_assign_slices(&mut z, &y0);
+        scale_slice(&mut z, alpha);
+        for i in 0..19 {
+            assert_eq!(z[i], (y0[i] * x[i] + x[i] - y0[i]) * alpha);
+        }
+    }
+
+    #[test]
+    fn test_eval_batch_matches_eval() {
+        let f = FFPoly::new(get_ffunits_in_range(3, 40), "x");
+        let points = get_ffunits_in_range(1000, 1013);
+        let evals = f.eval_batch(&points);
+        for (p, e) in points.iter().zip(evals.iter()) {
+            assert_eq!(f.eval(p), *e);
+        }
+    }
+}
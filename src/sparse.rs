@@ -0,0 +1,214 @@
+use crate::domain::Domain;
+use crate::error::Error;
+use crate::ffield_unit::FFieldUnit;
+use crate::field::Field;
+use crate::FFPoly::FFPoly;
+
+/// a polynomial stored as its non-zero terms (degree, coeff), sorted by degree.
+/// meant for vanishing polynomials like x^n - c, which would otherwise be n + 1
+/// mostly zero dense coefficients.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparsePoly<F: Field = FFieldUnit> {
+    pub var: String,
+    terms: Vec<(usize, F)>,
+}
+
+impl<F: Field> SparsePoly<F> {
+    /// terms may come in any order; repeated degrees are added up and zero terms dropped.
+    pub fn new(mut terms: Vec<(usize, F)>, var: &str) -> Self {
+        terms.sort_by_key(|(d, _)| *d);
+        let mut merged: Vec<(usize, F)> = Vec::with_capacity(terms.len());
+        for (d, c) in terms {
+            match merged.last_mut() {
+                Some((last_d, last_c)) if *last_d == d => *last_c = *last_c + c,
+                _ => merged.push((d, c)),
+            }
+        }
+        merged.retain(|(_, c)| !c.is_zero());
+        Self {
+            var: String::from(var),
+            terms: merged,
+        }
+    }
+
+    pub fn zero(var: &str) -> Self {
+        Self::new(vec![], var)
+    }
+
+    /// x^n - c
+    pub fn x_pow_minus(n: usize, c: F, var: &str) -> Self {
+        Self::new(vec![(n, F::one()), (0, c.neg())], var)
+    }
+
+    /// the polynomial vanishing exactly on domain: x^n - offset^n for offset * H, |H| = n.
+    pub fn vanishing(domain: &Domain<F>, var: &str) -> Self {
+        let n = domain.size();
+        Self::x_pow_minus(n, domain.offset().pow(n as u64), var)
+    }
+
+    pub fn terms(&self) -> &[(usize, F)] {
+        &self.terms
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn degree(&self) -> usize {
+        self.terms.last().map_or(0, |(d, _)| *d)
+    }
+
+    /// one exponentiation per term.
+    pub fn eval(&self, x: &F) -> F {
+        self.terms
+            .iter()
+            .fold(F::zero(), |acc, (d, c)| acc + *c * x.pow(*d as u64))
+    }
+
+    pub fn to_dense(&self) -> FFPoly<F> {
+        let mut coeffs = vec![F::zero(); self.degree() + 1];
+        for (d, c) in self.terms.iter() {
+            coeffs[*d] = *c;
+        }
+        FFPoly::new(coeffs, &self.var)
+    }
+}
+
+impl<F: Field> From<&FFPoly<F>> for SparsePoly<F> {
+    fn from(poly: &FFPoly<F>) -> Self {
+        let terms = poly.coeffs.iter().copied().enumerate().collect();
+        Self::new(terms, &poly.var)
+    }
+}
+
+impl<F: Field> FFPoly<F> {
+    /// quotient and remainder of self / divisor in O(deg(self) * #terms): long division
+    /// where every step only touches the divisor's non-zero terms.
+    pub fn try_qdiv_sparse(&self, divisor: &SparsePoly<F>) -> Result<(Self, Self), Error> {
+        let Some(&(d, lead)) = divisor.terms.last() else {
+            return Err(Error::DivisionByZero);
+        };
+        let var = self.var.as_str();
+        let mut rem = Self::new(self.coeffs.clone(), var).coeffs;
+        if rem.len() <= d {
+            return Ok((Self::zero(var), Self::new(rem, var)));
+        }
+
+        let lead_inv = lead.inverse();
+        let lower_terms = &divisor.terms[..divisor.terms.len() - 1];
+        let mut quot = vec![F::zero(); rem.len() - d];
+        for i in (0..quot.len()).rev() {
+            let c = rem[i + d] * lead_inv;
+            quot[i] = c;
+            rem[i + d] = F::zero();
+            if c.is_zero() {
+                continue;
+            }
+            for (e, t) in lower_terms {
+                rem[i + e] = rem[i + e] - c * *t;
+            }
+        }
+        rem.truncate(d);
+        Ok((Self::new(quot, var), Self::new(rem, var)))
+    }
+
+    /// same as `try_qdiv_sparse`, panics when divisor is zero.
+    pub fn qdiv_sparse(&self, divisor: &SparsePoly<F>) -> (Self, Self) {
+        self.try_qdiv_sparse(divisor)
+            .expect("polynomial division by the zero polynomial")
+    }
+
+    /// the exact quotient of self by the zerofier vanishing / prod (x - e) of a domain
+    /// with a few points left out, computed as self * prod (x - e) / vanishing so only
+    /// the short product is ever dense. Error::NotDivisible unless self vanishes on
+    /// every non-excluded point.
+    pub fn qdiv_zerofier(&self, vanishing: &SparsePoly<F>, excluded: &[F]) -> Result<Self, Error> {
+        let numerator = excluded.iter().fold(self.clone(), |acc, e| {
+            acc * FFPoly::gen_linear_term(e, &self.var)
+        });
+        // the remainder here is modulo vanishing, not modulo the zerofier, so it only
+        // tells whether the division is exact
+        let (q, r) = numerator.try_qdiv_sparse(vanishing)?;
+        if !r.coeffs.is_empty() {
+            return Err(Error::NotDivisible);
+        }
+        Ok(q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::get_ffunits_in_range;
+
+    #[test]
+    fn test_normalizes_terms() {
+        let one = FFieldUnit::one();
+        let p = SparsePoly::new(
+            vec![(5, one), (0, one), (5, one), (3, one), (3, one.neg())],
+            "x",
+        );
+        assert_eq!(p.terms(), &[(0, one), (5, FFieldUnit::new(2))]);
+        assert_eq!(p.degree(), 5);
+        assert!(SparsePoly::new(vec![(2, one), (2, one.neg())], "x").is_zero());
+    }
+
+    #[test]
+    fn test_eval_and_dense_round_trip() {
+        let p = SparsePoly::x_pow_minus(1024, FFieldUnit::new(3), "x");
+        let dense = p.to_dense();
+        assert_eq!(dense.coeffs.len(), 1025);
+        let x = FFieldUnit::new(98765);
+        assert_eq!(p.eval(&x), dense.eval(&x));
+        assert_eq!(SparsePoly::from(&dense), p);
+
+        let dom = Domain::<FFieldUnit>::coset(6, FFieldUnit::ffgenerator()).unwrap();
+        let z = SparsePoly::vanishing(&dom, "x");
+        assert!(dom.iter().all(|x| z.eval(&x).is_zero()));
+    }
+
+    #[test]
+    fn test_sparse_division_matches_dense() {
+        let f = FFPoly::new(get_ffunits_in_range(-2000, 100), "x");
+        let z = SparsePoly::new(
+            vec![
+                (1024, FFieldUnit::one()),
+                (17, FFieldUnit::new(5)),
+                (0, FFieldUnit::new(-1)),
+            ],
+            "x",
+        );
+        assert_eq!(f.qdiv_sparse(&z), f.qdiv(&z.to_dense()));
+        let small = FFPoly::new(get_ffunits_in_range(1, 10), "x");
+        assert_eq!(small.qdiv_sparse(&z), (FFPoly::zero("x"), small.clone()));
+        assert_eq!(
+            f.try_qdiv_sparse(&SparsePoly::zero("x")),
+            Err(Error::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_zerofier_division() {
+        let dom = Domain::<FFieldUnit>::subgroup(5).unwrap();
+        let xs = dom.elements();
+        // vanishes on xs[..30], i.e. on every point but the last two
+        let f = crate::FFPoly::prod(
+            &xs[..30]
+                .iter()
+                .map(|x| FFPoly::gen_linear_term(x, "x"))
+                .collect::<Vec<_>>(),
+            "x",
+        ) * FFPoly::new(get_ffunits_in_range(1, 8), "x");
+
+        let z = SparsePoly::vanishing(&dom, "x");
+        assert_eq!(
+            f.qdiv_zerofier(&z, &xs[30..]),
+            Ok(FFPoly::new(get_ffunits_in_range(1, 8), "x"))
+        );
+        assert_eq!(f.qdiv_zerofier(&z, &xs[31..]), Err(Error::NotDivisible));
+        assert_eq!(
+            f.qdiv_zerofier(&SparsePoly::zero("x"), &xs[30..]),
+            Err(Error::DivisionByZero)
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use crate::error::Error;
+use crate::field::Field;
+use crate::FFPoly::FFPoly;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+
+// p = 2^251 + 17 * 2^192 + 1, little-endian 64-bit limbs
+const P: [u64; 4] = [1, 0, 0, 0x0800_0000_0000_0011];
+// R = 2^256 mod p, the Montgomery form of one
+const R: [u64; 4] = [
+    0xffff_ffff_ffff_ffe1,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+    0x07ff_ffff_ffff_fdf0,
+];
+// R^2 mod p
+const R2: [u64; 4] = [
+    0xffff_fd73_7e00_0401,
+    0x0000_0001_330f_ffff,
+    0xffff_ffff_ff6f_8000,
+    0x07ff_d4ab_5e00_8810,
+];
+// -p^-1 mod 2^64
+const MU: u64 = u64::MAX;
+
+/// element of the Starknet/Cairo prime field, p = 2^251 + 17 * 2^192 + 1.
+/// stored as four 64-bit limbs in Montgomery form (x * 2^256 mod p).
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Stark252([u64; 4]);
+
+#[inline(always)]
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+#[inline(always)]
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+/// a + b * c + carry
+#[inline(always)]
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 * c as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+fn cmp_limbs(a: &[u64; 4], b: &[u64; 4]) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+fn add_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut r = [0u64; 4];
+    let mut carry = 0;
+    for i in 0..4 {
+        (r[i], carry) = adc(a[i], b[i], carry);
+    }
+    (r, carry)
+}
+
+fn sub_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut r = [0u64; 4];
+    let mut borrow = 0;
+    for i in 0..4 {
+        (r[i], borrow) = sbb(a[i], b[i], borrow);
+    }
+    (r, borrow)
+}
+
+/// subtracts p once if a >= p.
+fn reduce_once(a: [u64; 4]) -> [u64; 4] {
+    if cmp_limbs(&a, &P) != Ordering::Less {
+        sub_limbs(&a, &P).0
+    } else {
+        a
+    }
+}
+
+/// Montgomery product a * b * 2^-256 mod p, coarsely integrated operand scanning.
+fn mont_mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut t = [0u64; 6];
+    for &bi in b.iter() {
+        let mut carry = 0;
+        for j in 0..4 {
+            (t[j], carry) = mac(t[j], a[j], bi, carry);
+        }
+        (t[4], t[5]) = adc(t[4], carry, 0);
+
+        let m = t[0].wrapping_mul(MU);
+        let (_, mut carry) = mac(t[0], m, P[0], 0);
+        for j in 1..4 {
+            (t[j - 1], carry) = mac(t[j], m, P[j], carry);
+        }
+        (t[3], carry) = adc(t[4], carry, 0);
+        t[4] = t[5] + carry;
+        t[5] = 0;
+    }
+    let r = [t[0], t[1], t[2], t[3]];
+    if t[4] != 0 {
+        sub_limbs(&r, &P).0
+    } else {
+        reduce_once(r)
+    }
+}
+
+/// divides a by a small d in place, returns the remainder.
+fn div_rem_small(a: &mut [u64; 4], d: u64) -> u64 {
+    let mut rem = 0u128;
+    for limb in a.iter_mut().rev() {
+        let cur = (rem << 64) | *limb as u128;
+        *limb = (cur / d as u128) as u64;
+        rem = cur % d as u128;
+    }
+    rem as u64
+}
+
+/// a * m + c, returns None on overflow past 256 bits.
+fn mul_small_add(a: &[u64; 4], m: u64, c: u64) -> Option<[u64; 4]> {
+    let mut r = [0u64; 4];
+    let mut carry = c;
+    for i in 0..4 {
+        (r[i], carry) = mac(0, a[i], m, carry);
+    }
+    if carry == 0 {
+        Some(r)
+    } else {
+        None
+    }
+}
+
+impl Stark252 {
+    pub fn new(n: u128) -> Self {
+        Self::from_limbs([n as u64, (n >> 64) as u64, 0, 0]).unwrap()
+    }
+
+    /// from the little-endian limbs of a canonical value.
+    pub fn from_limbs(limbs: [u64; 4]) -> Result<Self, Error> {
+        if cmp_limbs(&limbs, &P) != Ordering::Less {
+            return Err(Error::NonCanonical);
+        }
+        Ok(Self(mont_mul(&limbs, &R2)))
+    }
+
+    /// little-endian limbs of the canonical representative.
+    pub fn to_limbs(&self) -> [u64; 4] {
+        mont_mul(&self.0, &[1, 0, 0, 0])
+    }
+
+    pub fn one() -> Self {
+        Self(R)
+    }
+
+    pub fn zero() -> Self {
+        Self([0; 4])
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    pub fn generator_value() -> u64 {
+        3
+    }
+
+    pub fn modulo_prime() -> [u64; 4] {
+        P
+    }
+
+    /// x^e for a 256-bit little-endian exponent.
+    pub fn pow_limbs(&self, e: &[u64; 4]) -> Self {
+        let mut tot = Self::one();
+        for limb in e.iter().rev() {
+            for bit in (0..64).rev() {
+                tot = tot * tot;
+                if (limb >> bit) & 1 == 1 {
+                    tot = tot * *self;
+                }
+            }
+        }
+        tot
+    }
+
+    pub fn inverse(&self) -> Self {
+        assert!(!self.is_zero(), "inverse of 0");
+        // Fermat: x^(p - 2)
+        let (p_minus_2, _) = sub_limbs(&P, &[2, 0, 0, 0]);
+        self.pow_limbs(&p_minus_2)
+    }
+
+    /// parses a hex string, with or without a leading 0x.
+    pub fn from_hex(s: &str) -> Result<Self, Error> {
+        let digits = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        Self::from_radix(digits, 16)
+    }
+
+    pub fn from_dec(s: &str) -> Result<Self, Error> {
+        Self::from_radix(s, 10)
+    }
+
+    fn from_radix(digits: &str, radix: u32) -> Result<Self, Error> {
+        if digits.is_empty() {
+            return Err(Error::InvalidDigit);
+        }
+        let mut acc = [0u64; 4];
+        for c in digits.chars() {
+            let d = c.to_digit(radix).ok_or(Error::InvalidDigit)?;
+            acc = mul_small_add(&acc, radix as u64, d as u64).ok_or(Error::NonCanonical)?;
+        }
+        Self::from_limbs(acc)
+    }
+
+    pub fn to_hex(&self) -> String {
+        let limbs = self.to_limbs();
+        let s = format!(
+            "{:016x}{:016x}{:016x}{:016x}",
+            limbs[3], limbs[2], limbs[1], limbs[0]
+        );
+        let trimmed = s.trim_start_matches('0');
+        format!("0x{}", if trimmed.is_empty() { "0" } else { trimmed })
+    }
+}
+
+impl Add for Stark252 {
+    type Output = Self;
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        // both operands are below p < 2^252, so the sum cannot carry out
+        let (s, _) = add_limbs(&self.0, &other.0);
+        Self(reduce_once(s))
+    }
+}
+
+impl Sub for Stark252 {
+    type Output = Self;
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        let (d, borrow) = sub_limbs(&self.0, &other.0);
+        if borrow == 1 {
+            Self(add_limbs(&d, &P).0)
+        } else {
+            Self(d)
+        }
+    }
+}
+
+impl Mul for Stark252 {
+    type Output = Self;
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        Self(mont_mul(&self.0, &other.0))
+    }
+}
+
+impl Div for Stark252 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inverse()
+    }
+}
+
+impl From<u64> for Stark252 {
+    fn from(n: u64) -> Self {
+        Self::new(n as u128)
+    }
+}
+
+impl FromStr for Stark252 {
+    type Err = Error;
+
+    /// hex when prefixed with 0x, decimal otherwise.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        if s.starts_with("0x") || s.starts_with("0X") {
+            Self::from_hex(s)
+        } else {
+            Self::from_dec(s)
+        }
+    }
+}
+
+impl fmt::Debug for Stark252 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Stark252({})", self.to_hex())
+    }
+}
+
+impl fmt::Display for Stark252 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // peel off 19 decimal digits at a time
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut n = self.to_limbs();
+        let mut chunks = vec![];
+        loop {
+            chunks.push(div_rem_small(&mut n, CHUNK));
+            if n == [0; 4] {
+                break;
+            }
+        }
+        let mut s = chunks.pop().unwrap().to_string();
+        for c in chunks.iter().rev() {
+            s.push_str(&format!("{:019}", c));
+        }
+        write!(f, "{}", s)
+    }
+}
+
+impl Field for Stark252 {
+    const TWO_ADICITY: u32 = 192;
+    const NUM_BYTES: usize = 32;
+
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn one() -> Self {
+        Self::one()
+    }
+
+    fn from_u128(n: u128) -> Self {
+        Self::new(n)
+    }
+
+    fn inverse(&self) -> Self {
+        self.inverse()
+    }
+
+    fn two_adic_generator() -> Self {
+        // 3^((p - 1) / 2^192), where (p - 1) / 2^192 = 2^59 + 17
+        Field::pow(&Self::new(3), (1 << 59) + 17)
+    }
+
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        self.to_limbs()
+            .iter()
+            .flat_map(|l| l.to_le_bytes())
+            .collect()
+    }
+
+    fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != 32 {
+            return Err(Error::InvalidLength);
+        }
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        Self::from_limbs(limbs)
+    }
+}
+
+impl Mul<FFPoly<Stark252>> for Stark252 {
+    type Output = FFPoly<Stark252>;
+
+    fn mul(self, other: FFPoly<Stark252>) -> FFPoly<Stark252> {
+        other.scalar_mul(&self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::MerkleTree;
+    use crate::FFPoly::interpolate_poly;
+
+    const P_DEC: &str =
+        "3618502788666131213697322783095070105623107215331596699973092056135872020481";
+
+    fn hex(s: &str) -> Stark252 {
+        Stark252::from_hex(s).unwrap()
+    }
+
+    #[test]
+    fn test_arithmetic_against_known_values() {
+        let a = hex("0x123456789abcdef0fedcba9876543210deadbeefcafebabe0123456789abcd");
+        let b = Stark252::zero() - Stark252::new(12345678901234567890);
+        assert_eq!(
+            (a * b).to_hex(),
+            "0x64b17831631f432c5c52fb860cb2e55aeeff21980a8a7a34dcb06e863b392c3"
+        );
+        assert_eq!(
+            (a + b).to_hex(),
+            "0x123456789abcdef0fedcba9876543210deadbeefcafeba12ac79b87c6aa0fb"
+        );
+        assert_eq!(
+            (b - a).to_hex(),
+            "0x7edcba9876543320f0123456789abcdef2152411035014496aa332dad574962"
+        );
+        assert_eq!(
+            a.inverse().to_hex(),
+            "0x4579e13bbe1744035c4ffbe4e3b5a74ce2355c07863532acd82bc20aa5a338b"
+        );
+        assert_eq!(a * a.inverse(), Stark252::one());
+    }
+
+    #[test]
+    fn test_parsing_and_display() {
+        let minus_one = Stark252::zero() - Stark252::one();
+        let p_minus_1 = P_DEC.replace("481", "480");
+        assert_eq!(minus_one.to_string(), p_minus_1);
+        assert_eq!(p_minus_1.parse::<Stark252>().unwrap(), minus_one);
+        assert_eq!(
+            "0x800000000000011000000000000000000000000000000000000000000000000"
+                .parse::<Stark252>()
+                .unwrap(),
+            minus_one
+        );
+        assert_eq!(Stark252::new(42).to_string(), "42");
+        assert_eq!(Stark252::zero().to_hex(), "0x0");
+        assert_eq!(P_DEC.parse::<Stark252>(), Err(Error::NonCanonical));
+        assert_eq!("12a".parse::<Stark252>(), Err(Error::InvalidDigit));
+        assert_eq!("".parse::<Stark252>(), Err(Error::InvalidDigit));
+    }
+
+    #[test]
+    fn test_two_adic_generator() {
+        let g = Stark252::two_adic_generator();
+        let half = (0..191).fold(g, |acc, _| acc.square());
+        assert_eq!(half, Stark252::zero() - Stark252::one());
+        assert_eq!(half.square(), Stark252::one());
+    }
+
+    #[test]
+    fn test_works_with_poly_and_merkle() {
+        let xs: Vec<Stark252> = (0..4).map(Stark252::from).collect();
+        let ys: Vec<Stark252> = xs.iter().map(|x| *x * *x + Stark252::from(3)).collect();
+        let f = interpolate_poly(&xs, &ys, "x");
+        assert_eq!(f.degree(), 2);
+        assert_eq!(f.eval(&Stark252::from(100)), Stark252::from(10003));
+
+        let mut tree = MerkleTree::new(&ys);
+        tree.build_tree();
+        assert_eq!(tree.height(), 2);
+        assert_eq!(Stark252::one().to_canonical_bytes()[0], 1);
+        let bytes = ys[3].to_canonical_bytes();
+        assert_eq!(Stark252::from_canonical_bytes(&bytes), Ok(ys[3]));
+        assert_eq!(
+            Stark252::from_canonical_bytes(&[0xff; 32]),
+            Err(Error::NonCanonical)
+        );
+    }
+}
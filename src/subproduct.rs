@@ -0,0 +1,204 @@
+use crate::field::{batch_inverse, Field};
+use crate::FFPoly::FFPoly;
+
+/// binary tree of products over x-values: the leaves are (x - x_i), every inner node
+/// is the product of its two children and the root is the vanishing polynomial of
+/// all the x-values. with fast multiplication and division it gives O(M(n) log n)
+/// evaluation at, and interpolation through, arbitrary points.
+#[derive(Debug, Clone)]
+pub struct SubproductTree<F: Field> {
+    var: String,
+    x_vals: Vec<F>,
+    /// layers[0] are the leaves; node j of layers[k + 1] covers nodes 2j and 2j + 1 of
+    /// layers[k], an unpaired last node is carried up unchanged.
+    layers: Vec<Vec<FFPoly<F>>>,
+}
+
+impl<F: Field> SubproductTree<F> {
+    pub fn new(x_vals: &[F], var: &str) -> Self {
+        let leaves: Vec<FFPoly<F>> = x_vals
+            .iter()
+            .map(|x| FFPoly::gen_linear_term(x, var))
+            .collect();
+        let mut layers = vec![leaves];
+        while layers.last().is_some_and(|l| l.len() > 1) {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => a.clone() * b.clone(),
+                    _ => pair[0].clone(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Self {
+            var: String::from(var),
+            x_vals: x_vals.to_vec(),
+            layers,
+        }
+    }
+
+    pub fn x_vals(&self) -> &[F] {
+        &self.x_vals
+    }
+
+    /// prod (x - x_i), None for an empty tree.
+    pub fn root(&self) -> Option<&FFPoly<F>> {
+        self.layers.last().and_then(|l| l.first())
+    }
+
+    /// f(x_i) for every x-value: f is reduced modulo the root, then each remainder
+    /// modulo the children of its node, down to the leaves where f mod (x - x_i) is
+    /// the constant f(x_i).
+    pub fn evaluate(&self, f: &FFPoly<F>) -> Vec<F> {
+        let Some(root) = self.root() else {
+            return vec![];
+        };
+        let mut rems = vec![f.qdiv_fast(root).1];
+        for layer in self.layers.iter().rev().skip(1) {
+            rems = layer
+                .iter()
+                .enumerate()
+                .map(|(i, node)| rems[i / 2].qdiv_fast(node).1)
+                .collect();
+        }
+        // a root of f leaves the zero polynomial, which has no coefficients
+        rems.iter()
+            .map(|r| r.coeffs.first().copied().unwrap_or_else(F::zero))
+            .collect()
+    }
+
+    /// the barycentric weights 1 / prod_{j != i} (x_i - x_j) = 1 / m'(x_i), with m the
+    /// root, in O(M(n) log n). panics if the x-values are not distinct.
+    pub fn weights(&self) -> Vec<F> {
+        let Some(root) = self.root() else {
+            return vec![];
+        };
+        // m'(x_i) is zero exactly when x_i is repeated
+        let derivs = self.evaluate(&root.derivative());
+        assert!(
+            derivs.iter().all(|d| !d.is_zero()),
+            "interpolation points must be distinct"
+        );
+        batch_inverse(&derivs)
+    }
+
+    /// the polynomial of degree < n through (x_i, y_vals[i]), in Lagrange form
+    /// sum_i y_i / m'(x_i) * m(x) / (x - x_i) with m the root, summed up the tree.
+    /// panics if the x-values are not distinct.
+    pub fn interpolate(&self, y_vals: &[F]) -> FFPoly<F> {
+        assert_eq!(y_vals.len(), self.x_vals.len(), "one value per x");
+        let var = self.var.as_str();
+        if self.root().is_none() {
+            return FFPoly::zero(var);
+        }
+
+        let weights = self.weights();
+        let mut combos: Vec<FFPoly<F>> = y_vals
+            .iter()
+            .zip(weights)
+            .map(|(y, w)| FFPoly::new(vec![*y * w], var))
+            .collect();
+        for layer in self.layers.iter().take(self.layers.len() - 1) {
+            combos = combos
+                .chunks(2)
+                .enumerate()
+                .map(|(j, pair)| match pair {
+                    [a, b] => {
+                        a.clone() * layer[2 * j + 1].clone() + b.clone() * layer[2 * j].clone()
+                    }
+                    _ => pair[0].clone(),
+                })
+                .collect();
+        }
+        combos.swap_remove(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffield_unit::FFieldUnit;
+    use crate::field::batch_inverse;
+    use crate::utils::get_ffunits_in_range;
+    use crate::FFPoly::{calc_langrange_polys, interpolate_lang_poly, lagrange_denominators};
+
+    fn points(n: i128) -> Vec<FFieldUnit> {
+        (0..n).map(|i| FFieldUnit::new(i * i * 7 + 3)).collect()
+    }
+
+    #[test]
+    fn test_root_is_vanishing_polynomial() {
+        let xs = points(11);
+        let tree = SubproductTree::new(&xs, "x");
+        let root = tree.root().unwrap();
+        assert_eq!(root.degree(), 11);
+        assert!(xs.iter().all(|x| root.eval(x).is_zero()));
+        assert!(SubproductTree::<FFieldUnit>::new(&[], "x").root().is_none());
+    }
+
+    #[test]
+    fn test_multipoint_evaluation() {
+        let f = FFPoly::new(get_ffunits_in_range(-300, 200), "x");
+        for n in [1, 2, 5, 64, 333] {
+            let xs = points(n);
+            let tree = SubproductTree::new(&xs, "x");
+            assert_eq!(tree.evaluate(&f), f.eval_batch(&xs));
+        }
+    }
+
+    #[test]
+    fn test_evaluation_at_roots() {
+        let f = FFPoly::new(vec![FFieldUnit::new(-3), FFieldUnit::one()], "x");
+        let xs = get_ffunits_in_range(1, 6);
+        let tree = SubproductTree::new(&xs, "x");
+        assert_eq!(tree.evaluate(&f), get_ffunits_in_range(-2, 3));
+
+        // every other point is a root
+        let xs = points(200);
+        let roots: Vec<FFieldUnit> = xs.iter().step_by(2).copied().collect();
+        let f = SubproductTree::new(&roots, "x").root().unwrap().clone();
+        let vals = SubproductTree::new(&xs, "x").evaluate(&f);
+        assert_eq!(vals, f.eval_batch(&xs));
+        assert!(vals.iter().step_by(2).all(|v| v.is_zero()));
+    }
+
+    #[test]
+    fn test_weights_match_lagrange_denominators() {
+        assert!(SubproductTree::<FFieldUnit>::new(&[], "x")
+            .weights()
+            .is_empty());
+        for n in [1, 2, 17, 40] {
+            let xs = points(n);
+            assert_eq!(
+                SubproductTree::new(&xs, "x").weights(),
+                batch_inverse(&lagrange_denominators(&xs))
+            );
+        }
+    }
+
+    #[test]
+    fn test_interpolation_matches_lagrange() {
+        for n in [1, 3, 17, 40] {
+            let xs = points(n);
+            let ys = get_ffunits_in_range(100, 100 + n);
+            let tree = SubproductTree::new(&xs, "x");
+            let f = tree.interpolate(&ys);
+            assert_eq!(
+                f,
+                interpolate_lang_poly(&ys, &calc_langrange_polys(&xs, "x"), "x")
+            );
+            assert_eq!(f.eval_batch(&xs), ys);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "interpolation points must be distinct")]
+    fn test_interpolation_rejects_repeated_points() {
+        let xs = get_ffunits_in_range(1, 5);
+        let tree = SubproductTree::new(&[xs.clone(), vec![xs[2]]].concat(), "x");
+        tree.interpolate(&get_ffunits_in_range(0, 5));
+    }
+}
@@ -25,5 +25,56 @@ pub fn u8_to_hexstr(v: &[u8]) -> String {
 }
 
 pub fn get_ffunits_in_range(s: i128, e: i128) -> Vec<FFieldUnit> {
-    (s..e).into_iter().map(|x| FFieldUnit::new(x)).collect()
+    (s..e).map(FFieldUnit::new).collect()
+}
+
+/// f(i, &items[i]) for every item, on up to jobs scoped threads each handling one
+/// contiguous chunk. the results are joined in chunk order, so the output does not
+/// depend on the number of threads.
+pub fn parallel_map<T, R, Fun>(items: &[T], jobs: usize, f: Fun) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    Fun: Fn(usize, &T) -> R + Sync,
+{
+    let chunk_len = items.len().div_ceil(jobs.max(1)).max(1);
+    let f = &f;
+    std::thread::scope(|s| {
+        let handles: Vec<_> = items
+            .chunks(chunk_len)
+            .enumerate()
+            .map(|(c, chunk)| {
+                s.spawn(move || {
+                    chunk
+                        .iter()
+                        .enumerate()
+                        .map(|(i, item)| f(c * chunk_len + i, item))
+                        .collect::<Vec<R>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("worker thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_map_keeps_order() {
+        let items: Vec<u64> = (0..103).collect();
+        let expected: Vec<u64> = items.iter().map(|x| x * x + 1).collect();
+        for jobs in [0, 1, 4, 7, 200] {
+            let res = parallel_map(&items, jobs, |i, x| {
+                assert_eq!(i as u64, *x);
+                x * x + 1
+            });
+            assert_eq!(res, expected);
+        }
+        assert!(parallel_map(&Vec::<u64>::new(), 4, |_, x| *x).is_empty());
+    }
 }